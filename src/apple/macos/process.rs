@@ -4,15 +4,23 @@
 // Copyright (c) 2021 Guillaume Gomez
 //
 
+use std::ffi::OsString;
+use std::io;
 use std::mem::{self, MaybeUninit};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use std::borrow::Borrow;
 
-use libc::{c_int, c_void, gid_t, kill, size_t, uid_t};
+use libc::{
+    c_int, c_void, gid_t, kill, mach_timebase_info, mach_timebase_info_data_t, size_t, uid_t,
+};
 
-use crate::{DiskUsage, Pid, ProcessExt, ProcessStatus, Signal};
+use crate::{
+    Container, ContextSwitches, CpuTime, DiskUsage, IoStats, Namespaces, NetworkUsage, PageFaults,
+    Pid, ProcessExt, ProcessStatus, SchedulingInfo, Signal, Uid,
+};
 
 use crate::sys::ffi;
 use crate::sys::process::ThreadStatus;
@@ -23,10 +31,12 @@ use crate::sys::system::Wrap;
 pub struct Process {
     pub(crate) name: String,
     pub(crate) cmd: Vec<String>,
+    pub(crate) cmd_os: Vec<OsString>,
     pub(crate) exe: PathBuf,
     pid: Pid,
     parent: Option<Pid>,
     pub(crate) environ: Vec<String>,
+    pub(crate) environ_os: Vec<OsString>,
     cwd: PathBuf,
     pub(crate) root: PathBuf,
     pub(crate) memory: u64,
@@ -61,7 +71,9 @@ impl Process {
             pid,
             parent: None,
             cmd: Vec::new(),
+            cmd_os: Vec::new(),
             environ: Vec::new(),
+            environ_os: Vec::new(),
             exe,
             cwd: PathBuf::new(),
             root: PathBuf::new(),
@@ -100,7 +112,9 @@ impl Process {
             name,
             pid,
             parent,
+            cmd_os: cmd.iter().map(OsString::from).collect(),
             cmd,
+            environ_os: environ.iter().map(OsString::from).collect(),
             environ,
             exe,
             cwd: PathBuf::new(),
@@ -133,7 +147,9 @@ impl ProcessExt for Process {
             pid,
             parent,
             cmd: Vec::new(),
+            cmd_os: Vec::new(),
             environ: Vec::new(),
+            environ_os: Vec::new(),
             exe: PathBuf::new(),
             cwd: PathBuf::new(),
             root: PathBuf::new(),
@@ -158,6 +174,10 @@ impl ProcessExt for Process {
     }
 
     fn kill(&self, signal: Signal) -> bool {
+        self.try_kill(signal).is_ok()
+    }
+
+    fn try_kill(&self, signal: Signal) -> io::Result<()> {
         let c_signal = match signal {
             Signal::Hangup => libc::SIGHUP,
             Signal::Interrupt => libc::SIGINT,
@@ -191,10 +211,19 @@ impl ProcessExt for Process {
             // SIGPOLL doesn't exist on apple targets but since it's an equivalent of SIGIO on unix,
             // we simply use the SIGIO constant.
             Signal::Poll => libc::SIGIO,
-            Signal::Power => return false,
+            Signal::Power => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "signal not supported on this platform",
+                ))
+            }
             Signal::Sys => libc::SIGSYS,
         };
-        unsafe { kill(self.pid, c_signal) == 0 }
+        if unsafe { kill(self.pid, c_signal) } == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
     }
 
     fn name(&self) -> &str {
@@ -217,6 +246,14 @@ impl ProcessExt for Process {
         &self.environ
     }
 
+    fn cmd_os(&self) -> &[OsString] {
+        &self.cmd_os
+    }
+
+    fn environ_os(&self) -> &[OsString] {
+        &self.environ_os
+    }
+
     fn cwd(&self) -> &Path {
         self.cwd.as_path()
     }
@@ -241,14 +278,71 @@ impl ProcessExt for Process {
         self.process_status
     }
 
+    fn user_id(&self) -> Option<Uid> {
+        Some(Uid(self.uid))
+    }
+
+    fn namespaces(&self) -> Namespaces {
+        Namespaces::default()
+    }
+
+    fn namespace_pid(&self) -> Option<Pid> {
+        None
+    }
+
+    fn container(&self) -> Option<&Container> {
+        None
+    }
+
+    fn systemd_unit(&self) -> Option<&str> {
+        None
+    }
+
+    fn systemd_slice(&self) -> Option<&str> {
+        None
+    }
+
+    fn cgroups(&self) -> &[String] {
+        &[]
+    }
+
+    fn session_id(&self) -> Option<Pid> {
+        None
+    }
+
+    fn process_group_id(&self) -> Option<Pid> {
+        None
+    }
+
+    fn tty(&self) -> Option<&Path> {
+        None
+    }
+
     fn start_time(&self) -> u64 {
         self.start_time
     }
 
+    fn start_time_precise(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(self.start_time)
+    }
+
+    fn run_time(&self) -> Duration {
+        SystemTime::now()
+            .duration_since(self.start_time_precise())
+            .unwrap_or_default()
+    }
+
     fn cpu_usage(&self) -> f32 {
         self.cpu_usage
     }
 
+    fn cpu_time(&self) -> CpuTime {
+        CpuTime {
+            user: Duration::from_nanos(self.utime),
+            system: Duration::from_nanos(self.stime),
+        }
+    }
+
     fn disk_usage(&self) -> DiskUsage {
         DiskUsage {
             read_bytes: self.read_bytes - self.old_read_bytes,
@@ -257,6 +351,72 @@ impl ProcessExt for Process {
             total_written_bytes: self.written_bytes,
         }
     }
+
+    fn io_stats(&self) -> IoStats {
+        IoStats::default()
+    }
+
+    fn network_usage(&self) -> NetworkUsage {
+        NetworkUsage::default()
+    }
+
+    fn scheduling(&self) -> SchedulingInfo {
+        SchedulingInfo::default()
+    }
+
+    fn cpu_affinity(&self) -> Vec<usize> {
+        Vec::new()
+    }
+
+    fn context_switches(&self) -> ContextSwitches {
+        ContextSwitches::default()
+    }
+
+    fn page_faults(&self) -> PageFaults {
+        PageFaults::default()
+    }
+
+    fn set_priority(&self, nice: i32) -> io::Result<()> {
+        if unsafe { libc::setpriority(libc::PRIO_PROCESS, self.pid as _, nice) } == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+
+    fn set_cpu_affinity(&self, _processors: &[usize]) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "CPU affinity cannot be set on this platform",
+        ))
+    }
+
+    fn suspend(&self) -> io::Result<()> {
+        self.try_kill(Signal::Stop)
+    }
+
+    fn resume(&self) -> io::Result<()> {
+        self.try_kill(Signal::Continue)
+    }
+
+    fn wait_for_exit(&self, timeout: Option<Duration>) -> io::Result<bool> {
+        let deadline = timeout.and_then(|timeout| Instant::now().checked_add(timeout));
+        loop {
+            if unsafe { kill(self.pid, 0) } != 0 {
+                let err = io::Error::last_os_error();
+                return match err.raw_os_error() {
+                    Some(libc::ESRCH) => Ok(true),
+                    _ => Err(err),
+                };
+            }
+            if let Some(deadline) = deadline {
+                if Instant::now() >= deadline {
+                    return Ok(false);
+                }
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
 }
 
 pub(crate) fn compute_cpu_usage(
@@ -266,6 +426,7 @@ pub(crate) fn compute_cpu_usage(
     user_time: u64,
     time_interval: Option<f64>,
 ) {
+    set_cpu_time(p, &task_info);
     if let Some(time_interval) = time_interval {
         let total_existing_time = p.old_stime + p.old_utime;
         if time_interval > 0.000001 && total_existing_time > 0 {
@@ -305,6 +466,26 @@ pub(crate) fn compute_cpu_usage(
     p.updated = true;
 }
 
+// The ratio to convert the mach absolute time units into nanoseconds.
+#[allow(deprecated)] // Everything related to mach_timebase_info_data_t
+static TIMEBASE: once_cell::sync::Lazy<(u64, u64)> = once_cell::sync::Lazy::new(|| {
+    let mut info = mach_timebase_info_data_t { numer: 0, denom: 0 };
+    if unsafe { mach_timebase_info(&mut info) } != ffi::KERN_SUCCESS || info.denom == 0 {
+        sysinfo_debug!("mach_timebase_info failed, using default value of 1");
+        return (1, 1);
+    }
+    (info.numer as u64, info.denom as u64)
+});
+
+/// The total times of `task_info` are in mach absolute time units, which are only nanoseconds on
+/// Intel CPUs.
+fn set_cpu_time(p: &mut Process, task_info: &libc::proc_taskinfo) {
+    let (numer, denom) = *TIMEBASE;
+    let to_ns = |time: u64| (time as u128 * numer as u128 / denom as u128) as u64;
+    p.utime = to_ns(task_info.pti_total_user);
+    p.stime = to_ns(task_info.pti_total_system);
+}
+
 /*pub fn set_time(p: &mut Process, utime: u64, stime: u64) {
     p.old_utime = p.utime;
     p.old_stime = p.stime;
@@ -557,6 +738,7 @@ pub(crate) fn update_process(
 
         let task_info = get_task_info(pid);
 
+        set_cpu_time(&mut p, &task_info);
         p.memory = task_info.pti_resident_size / 1_000;
         p.virtual_memory = task_info.pti_virtual_size / 1_000;
 
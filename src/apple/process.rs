@@ -9,7 +9,7 @@ use std::fmt;
 pub use crate::sys::inner::process::*;
 
 /// Enum describing the different status of a process.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ProcessStatus {
     /// Process being created by fork.
     Idle,
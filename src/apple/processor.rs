@@ -7,7 +7,7 @@
 use crate::sys::ffi;
 use crate::sys::system::get_sys_value;
 
-use crate::ProcessorExt;
+use crate::{FrequencyScaling, ProcessorExt};
 
 use libc::c_char;
 use std::mem;
@@ -116,6 +116,10 @@ impl ProcessorExt for Processor {
     fn brand(&self) -> &str {
         &self.brand
     }
+
+    fn frequency_scaling(&self) -> Option<FrequencyScaling> {
+        None
+    }
 }
 
 pub fn get_cpu_frequency() -> u64 {
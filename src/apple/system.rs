@@ -13,7 +13,11 @@ use crate::sys::processor::*;
 #[cfg(target_os = "macos")]
 use core_foundation_sys::base::{kCFAllocatorDefault, CFRelease};
 
-use crate::{LoadAvg, Pid, ProcessorExt, RefreshKind, SystemExt, User};
+use crate::{
+    CpuInfo, CpuTopology, CpuVulnerability, HugePages, Interrupt, KernelLimits, KernelStats,
+    LoadAvg, NumaNode, Pid, Pressure, ProcessRefreshKind, ProcessorExt, RefreshKind, SwapDevice,
+    SystemExt, User,
+};
 
 #[cfg(all(target_os = "macos", not(feature = "apple-sandbox")))]
 use crate::ProcessExt;
@@ -256,10 +260,10 @@ impl SystemExt for System {
     }
 
     #[cfg(any(target_os = "ios", feature = "apple-sandbox"))]
-    fn refresh_processes(&mut self) {}
+    fn refresh_processes_specifics(&mut self, _refresh_kind: ProcessRefreshKind) {}
 
     #[cfg(all(target_os = "macos", not(feature = "apple-sandbox")))]
-    fn refresh_processes(&mut self) {
+    fn refresh_processes_specifics(&mut self, _refresh_kind: ProcessRefreshKind) {
         use crate::utils::into_iter;
 
         let count = unsafe { ffi::proc_listallpids(::std::ptr::null_mut(), 0) };
@@ -293,12 +297,12 @@ impl SystemExt for System {
     }
 
     #[cfg(any(target_os = "ios", feature = "apple-sandbox"))]
-    fn refresh_process(&mut self, _: Pid) -> bool {
+    fn refresh_process_specifics(&mut self, _: Pid, _refresh_kind: ProcessRefreshKind) -> bool {
         false
     }
 
     #[cfg(all(target_os = "macos", not(feature = "apple-sandbox")))]
-    fn refresh_process(&mut self, pid: Pid) -> bool {
+    fn refresh_process_specifics(&mut self, pid: Pid, _refresh_kind: ProcessRefreshKind) -> bool {
         let arg_max = get_arg_max();
         let port = self.port;
         let time_interval = self.clock_info.as_mut().map(|c| c.get_time_interval(port));
@@ -315,6 +319,12 @@ impl SystemExt for System {
         }
     }
 
+    fn reload_process_info(&mut self, pid: Pid, refresh_kind: ProcessRefreshKind) -> bool {
+        // This information is only retrieved when the process is first listed.
+        self.process_list.remove(&pid);
+        self.refresh_process_specifics(pid, refresh_kind)
+    }
+
     #[cfg(target_os = "ios")]
     fn refresh_disks_list(&mut self) {}
 
@@ -330,6 +340,10 @@ impl SystemExt for System {
         self.users = crate::apple::users::get_users_list();
     }
 
+    fn refresh_kernel_stats(&mut self) {}
+
+    fn refresh_interrupts(&mut self) {}
+
     // COMMON PART
     //
     // Need to be moved into a "common" file to avoid duplication.
@@ -366,6 +380,18 @@ impl SystemExt for System {
         }
     }
 
+    fn cpu_topology(&self) -> Option<CpuTopology> {
+        None
+    }
+
+    fn cpu_info(&self) -> Option<CpuInfo> {
+        None
+    }
+
+    fn cpu_vulnerabilities(&self) -> HashMap<String, CpuVulnerability> {
+        HashMap::new()
+    }
+
     fn networks(&self) -> &Networks {
         &self.networks
     }
@@ -403,6 +429,22 @@ impl SystemExt for System {
         self.swap_total - self.swap_free
     }
 
+    fn swap_devices(&self) -> Vec<SwapDevice> {
+        Vec::new()
+    }
+
+    fn hugepages(&self) -> HugePages {
+        HugePages::default()
+    }
+
+    fn numa_nodes(&self) -> Vec<NumaNode> {
+        Vec::new()
+    }
+
+    fn kernel_limits(&self) -> KernelLimits {
+        KernelLimits::default()
+    }
+
     fn components(&self) -> &[Component] {
         &self.components
     }
@@ -437,6 +479,26 @@ impl SystemExt for System {
         }
     }
 
+    fn pressure(&self) -> Option<Pressure> {
+        None
+    }
+
+    fn cgroup_pressure(&self, _cgroup: &str) -> Option<Pressure> {
+        None
+    }
+
+    fn kernel_stats(&self) -> KernelStats {
+        KernelStats::default()
+    }
+
+    fn interrupts(&self) -> &[Interrupt] {
+        &[]
+    }
+
+    fn softirqs(&self) -> &[Interrupt] {
+        &[]
+    }
+
     fn users(&self) -> &[User] {
         &self.users
     }
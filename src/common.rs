@@ -4,7 +4,12 @@
 // Copyright (c) 2015 Guillaume Gomez
 //
 
-use crate::{NetworkData, Networks, NetworksExt, UserExt};
+use crate::{NetworkData, Networks, NetworksExt, Process, ProcessExt, ProcessStatus, UserExt};
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
 
 /// Trait to have a common fallback for the [`Pid`][crate::Pid] type.
 pub trait AsU32 {
@@ -37,13 +42,13 @@ cfg_if::cfg_if! {
 }
 
 macro_rules! impl_get_set {
-    ($name:ident, $with:ident, $without:ident) => {
+    ($ty_name:ident, $name:ident, $with:ident, $without:ident) => {
         #[doc = concat!("Returns the value of the \"", stringify!($name), "\" refresh kind.
 
 ```
-use sysinfo::RefreshKind;
+use sysinfo::", stringify!($ty_name), ";
 
-let r = RefreshKind::new();
+let r = ", stringify!($ty_name), "::new();
 assert_eq!(r.", stringify!($name), "(), false);
 
 let r = r.with_", stringify!($name), "();
@@ -59,15 +64,15 @@ assert_eq!(r.", stringify!($name), "(), false);
         #[doc = concat!("Sets the value of the \"", stringify!($name), "\" refresh kind to `true`.
 
 ```
-use sysinfo::RefreshKind;
+use sysinfo::", stringify!($ty_name), ";
 
-let r = RefreshKind::new();
+let r = ", stringify!($ty_name), "::new();
 assert_eq!(r.", stringify!($name), "(), false);
 
 let r = r.with_", stringify!($name), "();
 assert_eq!(r.", stringify!($name), "(), true);
 ```")]
-        pub fn $with(mut self) -> RefreshKind {
+        pub fn $with(mut self) -> $ty_name {
             self.$name = true;
             self
         }
@@ -75,21 +80,153 @@ assert_eq!(r.", stringify!($name), "(), true);
         #[doc = concat!("Sets the value of the \"", stringify!($name), "\" refresh kind to `false`.
 
 ```
-use sysinfo::RefreshKind;
+use sysinfo::", stringify!($ty_name), ";
 
-let r = RefreshKind::everything();
+let r = ", stringify!($ty_name), "::everything();
 assert_eq!(r.", stringify!($name), "(), true);
 
 let r = r.without_", stringify!($name), "();
 assert_eq!(r.", stringify!($name), "(), false);
 ```")]
-        pub fn $without(mut self) -> RefreshKind {
+        pub fn $without(mut self) -> $ty_name {
             self.$name = false;
             self
         }
     };
 }
 
+/// Used to determine what you want to refresh specifically on the [`Process`] type.
+///
+/// Whatever the refresh kind, the CPU usage, the memory usage, the status, the scheduling
+/// information and the page faults of the processes are always refreshed. The command line, the
+/// environment, the current working directory and the root directory of a process are only
+/// retrieved when the process is first listed (use [`SystemExt::reload_process_info`] to retrieve
+/// them again). The cgroup based information (like the container) is retrieved at every refresh
+/// since the process can be moved to another cgroup.
+///
+/// The network usage and the namespaces (which are retrieved at every refresh since they can
+/// change without the process executing another program) are expensive to retrieve so they
+/// aren't part of [`SystemExt::refresh_processes`] nor of [`SystemExt::refresh_process`].
+///
+/// ```
+/// use sysinfo::{ProcessRefreshKind, System, SystemExt};
+///
+/// let mut system = System::new();
+///
+/// // We don't want to go through the tasks of the processes nor to get their disk usage.
+/// system.refresh_processes_specifics(
+///     ProcessRefreshKind::everything()
+///         .without_tasks()
+///         .without_disk_usage(),
+/// );
+/// ```
+///
+/// [`Process`]: crate::Process
+/// [`SystemExt::reload_process_info`]: crate::SystemExt::reload_process_info
+/// [`SystemExt::refresh_processes`]: crate::SystemExt::refresh_processes
+/// [`SystemExt::refresh_process`]: crate::SystemExt::refresh_process
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProcessRefreshKind {
+    tasks: bool,
+    cmd: bool,
+    environ: bool,
+    cwd: bool,
+    root: bool,
+    disk_usage: bool,
+    context_switches: bool,
+    network_usage: bool,
+    namespaces: bool,
+    cgroup: bool,
+}
+
+impl ProcessRefreshKind {
+    /// Creates a new `ProcessRefreshKind` with every refresh set to `false`.
+    ///
+    /// ```
+    /// use sysinfo::ProcessRefreshKind;
+    ///
+    /// let r = ProcessRefreshKind::new();
+    ///
+    /// assert_eq!(r.tasks(), false);
+    /// assert_eq!(r.cmd(), false);
+    /// assert_eq!(r.environ(), false);
+    /// assert_eq!(r.cwd(), false);
+    /// assert_eq!(r.root(), false);
+    /// assert_eq!(r.disk_usage(), false);
+    /// assert_eq!(r.context_switches(), false);
+    /// assert_eq!(r.network_usage(), false);
+    /// assert_eq!(r.namespaces(), false);
+    /// assert_eq!(r.cgroup(), false);
+    /// ```
+    pub fn new() -> ProcessRefreshKind {
+        ProcessRefreshKind::default()
+    }
+
+    /// Creates a new `ProcessRefreshKind` with every refresh set to `true`.
+    ///
+    /// ```
+    /// use sysinfo::ProcessRefreshKind;
+    ///
+    /// let r = ProcessRefreshKind::everything();
+    ///
+    /// assert_eq!(r.tasks(), true);
+    /// assert_eq!(r.cmd(), true);
+    /// assert_eq!(r.environ(), true);
+    /// assert_eq!(r.cwd(), true);
+    /// assert_eq!(r.root(), true);
+    /// assert_eq!(r.disk_usage(), true);
+    /// assert_eq!(r.context_switches(), true);
+    /// assert_eq!(r.network_usage(), true);
+    /// assert_eq!(r.namespaces(), true);
+    /// assert_eq!(r.cgroup(), true);
+    /// ```
+    pub fn everything() -> ProcessRefreshKind {
+        ProcessRefreshKind {
+            tasks: true,
+            cmd: true,
+            environ: true,
+            cwd: true,
+            root: true,
+            disk_usage: true,
+            context_switches: true,
+            network_usage: true,
+            namespaces: true,
+            cgroup: true,
+        }
+    }
+
+    impl_get_set!(ProcessRefreshKind, tasks, with_tasks, without_tasks);
+    impl_get_set!(ProcessRefreshKind, cmd, with_cmd, without_cmd);
+    impl_get_set!(ProcessRefreshKind, environ, with_environ, without_environ);
+    impl_get_set!(ProcessRefreshKind, cwd, with_cwd, without_cwd);
+    impl_get_set!(ProcessRefreshKind, root, with_root, without_root);
+    impl_get_set!(
+        ProcessRefreshKind,
+        disk_usage,
+        with_disk_usage,
+        without_disk_usage
+    );
+    impl_get_set!(
+        ProcessRefreshKind,
+        context_switches,
+        with_context_switches,
+        without_context_switches
+    );
+    impl_get_set!(
+        ProcessRefreshKind,
+        network_usage,
+        with_network_usage,
+        without_network_usage
+    );
+    impl_get_set!(
+        ProcessRefreshKind,
+        namespaces,
+        with_namespaces,
+        without_namespaces
+    );
+    impl_get_set!(ProcessRefreshKind, cgroup, with_cgroup, without_cgroup);
+}
+
 /// Used to determine what you want to refresh specifically on [`System`] type.
 ///
 /// ```
@@ -117,6 +254,8 @@ pub struct RefreshKind {
     components: bool,
     components_list: bool,
     users_list: bool,
+    kernel_stats: bool,
+    interrupts: bool,
 }
 
 impl RefreshKind {
@@ -137,6 +276,8 @@ impl RefreshKind {
     /// assert_eq!(r.components(), false);
     /// assert_eq!(r.components_list(), false);
     /// assert_eq!(r.users_list(), false);
+    /// assert_eq!(r.kernel_stats(), false);
+    /// assert_eq!(r.interrupts(), false);
     /// ```
     pub fn new() -> RefreshKind {
         RefreshKind::default()
@@ -159,6 +300,8 @@ impl RefreshKind {
     /// assert_eq!(r.components(), true);
     /// assert_eq!(r.components_list(), true);
     /// assert_eq!(r.users_list(), true);
+    /// assert_eq!(r.kernel_stats(), true);
+    /// assert_eq!(r.interrupts(), true);
     /// ```
     pub fn everything() -> RefreshKind {
         RefreshKind {
@@ -172,23 +315,38 @@ impl RefreshKind {
             components: true,
             components_list: true,
             users_list: true,
+            kernel_stats: true,
+            interrupts: true,
         }
     }
 
-    impl_get_set!(networks, with_networks, without_networks);
-    impl_get_set!(networks_list, with_networks_list, without_networks_list);
-    impl_get_set!(processes, with_processes, without_processes);
-    impl_get_set!(disks, with_disks, without_disks);
-    impl_get_set!(disks_list, with_disks_list, without_disks_list);
-    impl_get_set!(memory, with_memory, without_memory);
-    impl_get_set!(cpu, with_cpu, without_cpu);
-    impl_get_set!(components, with_components, without_components);
+    impl_get_set!(RefreshKind, networks, with_networks, without_networks);
     impl_get_set!(
+        RefreshKind,
+        networks_list,
+        with_networks_list,
+        without_networks_list
+    );
+    impl_get_set!(RefreshKind, processes, with_processes, without_processes);
+    impl_get_set!(RefreshKind, disks, with_disks, without_disks);
+    impl_get_set!(RefreshKind, disks_list, with_disks_list, without_disks_list);
+    impl_get_set!(RefreshKind, memory, with_memory, without_memory);
+    impl_get_set!(RefreshKind, cpu, with_cpu, without_cpu);
+    impl_get_set!(RefreshKind, components, with_components, without_components);
+    impl_get_set!(
+        RefreshKind,
         components_list,
         with_components_list,
         without_components_list
     );
-    impl_get_set!(users_list, with_users_list, without_users_list);
+    impl_get_set!(RefreshKind, users_list, with_users_list, without_users_list);
+    impl_get_set!(
+        RefreshKind,
+        kernel_stats,
+        with_kernel_stats,
+        without_kernel_stats
+    );
+    impl_get_set!(RefreshKind, interrupts, with_interrupts, without_interrupts);
 }
 
 /// Iterator over network interfaces.
@@ -351,6 +509,663 @@ pub struct LoadAvg {
     pub fifteen: f64,
 }
 
+/// Pressure stall statistics of a category of tasks (see [`ResourcePressure`]).
+///
+/// The averages are percentages of wall time during which tasks were stalled.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PressureStats {
+    /// Share of time stalled within the last ten seconds.
+    pub avg10: f64,
+    /// Share of time stalled within the last minute.
+    pub avg60: f64,
+    /// Share of time stalled within the last five minutes.
+    pub avg300: f64,
+    /// Total stall time (in microseconds).
+    pub total: u64,
+}
+
+/// Pressure stall information of a resource.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ResourcePressure {
+    /// Time during which at least one task was stalled on the resource.
+    pub some: PressureStats,
+    /// Time during which all non-idle tasks were stalled on the resource at the same time.
+    ///
+    /// It is `None` for the CPU on kernels older than 5.13.
+    pub full: Option<PressureStats>,
+}
+
+/// Pressure stall information (PSI) of the CPU, memory and I/O.
+///
+/// A resource is `None` if its information isn't available, like for a cgroup whose controller
+/// of this resource isn't enabled.
+///
+/// It is returned by [`SystemExt::pressure`][crate::SystemExt::pressure] and
+/// [`SystemExt::cgroup_pressure`][crate::SystemExt::cgroup_pressure].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new();
+/// if let Some(memory) = s.pressure().and_then(|pressure| pressure.memory) {
+///     println!(
+///         "memory: {}% (some), {:?} (full)",
+///         memory.some.avg10,
+///         memory.full.map(|full| full.avg10),
+///     );
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Pressure {
+    /// Pressure on the CPU.
+    pub cpu: Option<ResourcePressure>,
+    /// Pressure on the memory.
+    pub memory: Option<ResourcePressure>,
+    /// Pressure on the I/O.
+    pub io: Option<ResourcePressure>,
+}
+
+/// Kernel activity counters.
+///
+/// It is returned by [`SystemExt::kernel_stats`][crate::SystemExt::kernel_stats] and updated by
+/// [`SystemExt::refresh_kernel_stats`][crate::SystemExt::refresh_kernel_stats].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let mut s = System::new();
+/// s.refresh_kernel_stats();
+/// std::thread::sleep(std::time::Duration::from_secs(1));
+/// s.refresh_kernel_stats();
+/// let stats = s.kernel_stats();
+/// println!("context switches: {}/s", stats.context_switches);
+/// println!("swapped in pages: {}/s", stats.swapped_in_pages);
+/// println!("OOM kills: new/total => {}/{}", stats.oom_kills, stats.total_oom_kills);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct KernelStats {
+    /// Total number of context switches since boot.
+    pub total_context_switches: u64,
+    /// Number of context switches since the last refresh.
+    pub context_switches: u64,
+    /// Total number of interrupts serviced since boot.
+    pub total_interrupts: u64,
+    /// Number of interrupts serviced since the last refresh.
+    pub interrupts: u64,
+    /// Total number of processes and threads created since boot.
+    pub total_forks: u64,
+    /// Number of processes and threads created since the last refresh.
+    pub forks: u64,
+    /// Number of threads currently runnable.
+    pub procs_running: u64,
+    /// Number of threads currently blocked, waiting for I/O to complete.
+    pub procs_blocked: u64,
+    /// Total number of page faults since boot.
+    pub total_page_faults: u64,
+    /// Number of page faults since the last refresh.
+    pub page_faults: u64,
+    /// Total number of major page faults (the ones which required loading a page from disk)
+    /// since boot.
+    pub total_major_page_faults: u64,
+    /// Number of major page faults since the last refresh.
+    pub major_page_faults: u64,
+    /// Total number of pages swapped in since boot.
+    pub total_swapped_in_pages: u64,
+    /// Number of pages swapped in since the last refresh.
+    pub swapped_in_pages: u64,
+    /// Total number of pages swapped out since boot.
+    pub total_swapped_out_pages: u64,
+    /// Number of pages swapped out since the last refresh.
+    pub swapped_out_pages: u64,
+    /// Total number of pages scanned by the page reclaim since boot.
+    pub total_scanned_pages: u64,
+    /// Number of pages scanned by the page reclaim since the last refresh.
+    pub scanned_pages: u64,
+    /// Total number of pages reclaimed since boot.
+    pub total_reclaimed_pages: u64,
+    /// Number of pages reclaimed since the last refresh.
+    pub reclaimed_pages: u64,
+    /// Total number of processes killed by the OOM killer since boot.
+    pub total_oom_kills: u64,
+    /// Number of processes killed by the OOM killer since the last refresh.
+    pub oom_kills: u64,
+}
+
+/// Counts of an interrupt or of a softirq on each processor.
+///
+/// It is returned by [`SystemExt::interrupts`][crate::SystemExt::interrupts] and
+/// [`SystemExt::softirqs`][crate::SystemExt::softirqs].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let mut s = System::new();
+/// s.refresh_interrupts();
+/// std::thread::sleep(std::time::Duration::from_secs(1));
+/// s.refresh_interrupts();
+/// for interrupt in s.interrupts() {
+///     println!("{} {:?}: {:?}", interrupt.name, interrupt.devices, interrupt.per_cpu);
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Interrupt {
+    /// Name of the interrupt: the IRQ number (like `"24"`), a short name for architecture
+    /// specific interrupts (like `"LOC"`) or the name of the softirq (like `"NET_RX"`).
+    pub name: String,
+    /// Description of the interrupt, like `"IO-APIC 2-edge timer"` or `"Local timer interrupts"`.
+    /// It is empty for softirqs.
+    pub description: String,
+    /// Names of the devices using the interrupt.
+    pub devices: Vec<String>,
+    /// Ids of the processors the counts belong to (`N` in `cpuN`), in the same order as
+    /// `total_per_cpu` and `per_cpu`.
+    ///
+    /// The hardware interrupts are only listed for the online processors whereas the softirqs
+    /// are listed for all the possible ones, so they don't always match
+    /// [`SystemExt::processors`][crate::SystemExt::processors].
+    ///
+    /// It is empty for the interrupts (like `"ERR"` and `"MIS"` on x86) which only have a
+    /// single count for all the processors.
+    pub cpus: Vec<usize>,
+    /// Total count on each processor listed in `cpus`, or the single count for all of them.
+    pub total_per_cpu: Vec<u64>,
+    /// Count on each processor listed in `cpus` since the last refresh.
+    pub per_cpu: Vec<u64>,
+}
+
+/// Type of a [`CpuCache`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheType {
+    /// Data cache.
+    Data,
+    /// Instruction cache.
+    Instruction,
+    /// Cache for both data and instructions.
+    Unified,
+    /// Unknown cache type.
+    Unknown,
+}
+
+/// Cache used by a [`LogicalCpu`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CpuCache {
+    /// Level of the cache (1 for L1, 2 for L2...).
+    pub level: u32,
+    /// Type of the cache.
+    pub kind: CacheType,
+    /// Size of the cache (in bytes).
+    pub size: u64,
+    /// Size of a cache line (in bytes).
+    pub line_size: Option<u64>,
+    /// Number of ways of associativity.
+    pub ways_of_associativity: Option<u32>,
+    /// Logical CPUs sharing this cache, sorted.
+    pub shared_cpus: Vec<usize>,
+}
+
+/// Topology of a logical CPU.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogicalCpu {
+    /// Identifier of the logical CPU (`N` in `cpuN`).
+    pub id: usize,
+    /// Identifier of the physical package (socket).
+    pub package_id: Option<u32>,
+    /// Identifier of the die in the package.
+    pub die_id: Option<u32>,
+    /// Identifier of the core in the package (or in the die).
+    pub core_id: Option<u32>,
+    /// Logical CPUs of the same core, including this one, sorted. There is more than one when
+    /// simultaneous multithreading (hyper-threading) is enabled.
+    pub thread_siblings: Vec<usize>,
+    /// Logical CPUs of the same package, including this one, sorted.
+    pub package_cpus: Vec<usize>,
+    /// NUMA node of the logical CPU.
+    pub numa_node: Option<u32>,
+    /// Caches used by the logical CPU, sorted by level.
+    pub caches: Vec<CpuCache>,
+}
+
+impl LogicalCpu {
+    /// Returns `true` if this logical CPU isn't the first one of its core, in other words if it
+    /// is an additional hardware thread (a "hyper-thread").
+    ///
+    /// ```
+    /// use sysinfo::LogicalCpu;
+    ///
+    /// let cpu = LogicalCpu {
+    ///     id: 4,
+    ///     package_id: Some(0),
+    ///     die_id: Some(0),
+    ///     core_id: Some(0),
+    ///     thread_siblings: vec![0, 4],
+    ///     package_cpus: (0..8).collect(),
+    ///     numa_node: Some(0),
+    ///     caches: Vec::new(),
+    /// };
+    /// assert!(cpu.is_smt_sibling());
+    /// ```
+    pub fn is_smt_sibling(&self) -> bool {
+        self.thread_siblings
+            .first()
+            .map(|first| *first != self.id)
+            .unwrap_or(false)
+    }
+}
+
+/// Topology of the CPUs: packages, cores, hardware threads, NUMA nodes and caches.
+///
+/// It is returned by [`SystemExt::cpu_topology`][crate::SystemExt::cpu_topology].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new();
+/// if let Some(topology) = s.cpu_topology() {
+///     for cpu in &topology.cpus {
+///         println!(
+///             "cpu{}: package {:?}, core {:?}, node {:?}, siblings {:?}",
+///             cpu.id, cpu.package_id, cpu.core_id, cpu.numa_node, cpu.thread_siblings,
+///         );
+///     }
+///     println!("offline CPUs: {:?}", topology.offline);
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CpuTopology {
+    /// Online logical CPUs, sorted by identifier.
+    pub cpus: Vec<LogicalCpu>,
+    /// Identifiers of the online logical CPUs.
+    pub online: Vec<usize>,
+    /// Identifiers of the offline logical CPUs.
+    pub offline: Vec<usize>,
+    /// Identifiers of the logical CPUs physically present.
+    pub present: Vec<usize>,
+    /// Identifiers of the logical CPUs which could be present (with CPU hotplug).
+    pub possible: Vec<usize>,
+}
+
+impl CpuTopology {
+    /// Returns the logical CPU with the given identifier, if it is online.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(cpu) = s.cpu_topology().and_then(|topology| topology.cpu(0).cloned()) {
+    ///     println!("cpu0 is in package {:?}", cpu.package_id);
+    /// }
+    /// ```
+    pub fn cpu(&self, id: usize) -> Option<&LogicalCpu> {
+        self.cpus
+            .binary_search_by_key(&id, |cpu| cpu.id)
+            .ok()
+            .map(|index| &self.cpus[index])
+    }
+}
+
+/// Time spent by a processor at a given frequency.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FrequencyState {
+    /// Frequency (in MHz).
+    pub frequency: u64,
+    /// Time spent at this frequency since boot.
+    pub time: Duration,
+}
+
+/// Frequency scaling (cpufreq) information of a processor.
+///
+/// It is returned by [`ProcessorExt::frequency_scaling`][crate::ProcessorExt::frequency_scaling].
+/// All the frequencies are in MHz.
+///
+/// ```no_run
+/// use sysinfo::{ProcessorExt, System, SystemExt};
+///
+/// let s = System::new();
+/// for processor in s.processors() {
+///     if let Some(scaling) = processor.frequency_scaling() {
+///         println!(
+///             "{}: {:?} governor, {:?}-{:?} MHz",
+///             processor.name(),
+///             scaling.governor,
+///             scaling.min_frequency,
+///             scaling.max_frequency,
+///         );
+///     }
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FrequencyScaling {
+    /// Name of the scaling driver (like `intel_pstate` or `acpi-cpufreq`).
+    pub driver: Option<String>,
+    /// Current frequency.
+    pub current_frequency: Option<u64>,
+    /// Minimum frequency supported by the hardware.
+    pub min_frequency: Option<u64>,
+    /// Maximum frequency supported by the hardware (including boost frequencies).
+    pub max_frequency: Option<u64>,
+    /// Base (non-boosted) frequency. Only some drivers provide it, like `intel_pstate`.
+    pub base_frequency: Option<u64>,
+    /// Minimum frequency allowed by the current policy.
+    pub scaling_min_frequency: Option<u64>,
+    /// Maximum frequency allowed by the current policy.
+    pub scaling_max_frequency: Option<u64>,
+    /// Current scaling governor (like `performance` or `powersave`).
+    pub governor: Option<String>,
+    /// Scaling governors which can be used.
+    pub available_governors: Vec<String>,
+    /// Current energy performance preference (like `performance` or `balance_power`). Only some
+    /// drivers provide it, like `intel_pstate` and `amd-pstate`.
+    pub energy_performance_preference: Option<String>,
+    /// Whether the frequency can go above the base frequency (turbo boost), for the whole
+    /// system.
+    pub boost: Option<bool>,
+    /// Time spent at each frequency, if the driver provides these statistics.
+    pub time_in_state: Vec<FrequencyState>,
+    /// Number of frequency changes, if the driver provides these statistics.
+    pub transitions: Option<u64>,
+}
+
+/// Identification and features of the processors.
+///
+/// It is returned by [`SystemExt::cpu_info`][crate::SystemExt::cpu_info] and describes the
+/// first processor (they are expected to be the same).
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new();
+/// if let Some(info) = s.cpu_info() {
+///     println!("{} (family {:?}, model {:?})", info.brand, info.family, info.model);
+///     if info.has_flag("avx512f") {
+///         println!("AVX-512 is supported");
+///     }
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CpuInfo {
+    /// Vendor identifier (like `GenuineIntel` or `AuthenticAMD`).
+    pub vendor_id: String,
+    /// Brand (model name) of the processor.
+    pub brand: String,
+    /// Family of the processor.
+    pub family: Option<u32>,
+    /// Model of the processor (within its family).
+    pub model: Option<u32>,
+    /// Stepping (revision) of the processor.
+    pub stepping: Option<u32>,
+    /// Revision of the loaded microcode.
+    pub microcode: Option<u64>,
+    /// Features supported by the processor (like `avx2`, `aes` or `neon`).
+    pub flags: HashSet<String>,
+    /// Hardware bugs the processor is affected by (like `spectre_v1`), see
+    /// [`SystemExt::cpu_vulnerabilities`][crate::SystemExt::cpu_vulnerabilities] for their
+    /// mitigations.
+    pub bugs: HashSet<String>,
+    /// BogoMIPS measured by the kernel at boot.
+    pub bogomips: Option<f64>,
+    /// Number of bits of the physical addresses.
+    pub physical_address_bits: Option<u32>,
+    /// Number of bits of the virtual addresses.
+    pub virtual_address_bits: Option<u32>,
+}
+
+impl CpuInfo {
+    /// Returns `true` if the processor supports the given feature.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// let has_avx2 = s.cpu_info().map(|info| info.has_flag("avx2")).unwrap_or(false);
+    /// ```
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.contains(flag)
+    }
+}
+
+/// Status of a processor against a hardware vulnerability.
+///
+/// It is returned by [`SystemExt::cpu_vulnerabilities`][crate::SystemExt::cpu_vulnerabilities].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CpuVulnerability {
+    /// The processor isn't affected.
+    NotAffected,
+    /// The processor is affected and the vulnerability is mitigated, with the mitigation used.
+    Mitigated(String),
+    /// The processor is affected and the vulnerability isn't mitigated, with the details given
+    /// by the kernel (which can be empty).
+    Vulnerable(String),
+    /// Any other status (like `Unknown: Dependent on hypervisor status`).
+    Unknown(String),
+}
+
+impl CpuVulnerability {
+    /// Returns `true` if the processor is affected and the vulnerability isn't mitigated.
+    ///
+    /// ```
+    /// use sysinfo::CpuVulnerability;
+    ///
+    /// assert!(CpuVulnerability::Vulnerable(String::new()).is_vulnerable());
+    /// assert!(!CpuVulnerability::Mitigated("PTI".to_owned()).is_vulnerable());
+    /// ```
+    pub fn is_vulnerable(&self) -> bool {
+        matches!(self, CpuVulnerability::Vulnerable(_))
+    }
+}
+
+/// Kind of a [`SwapDevice`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SwapKind {
+    /// Disk partition.
+    Partition,
+    /// Swap file.
+    File,
+    /// Compressed RAM block device.
+    Zram,
+    /// Unknown swap kind.
+    Unknown,
+}
+
+/// Compression statistics of a zram device (all sizes are in KB).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ZramStats {
+    /// Size of the data stored in the device, before compression.
+    pub original_size: u64,
+    /// Size of the data stored in the device, after compression.
+    pub compressed_size: u64,
+    /// Memory used by the device, including its metadata and fragmentation.
+    pub memory_used: u64,
+}
+
+/// Swap area (partition, file or zram device).
+///
+/// It is returned by [`SystemExt::swap_devices`][crate::SystemExt::swap_devices].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new();
+/// for device in s.swap_devices() {
+///     println!("{:?}: {}/{} KB used", device.path, device.used, device.size);
+///     if let Some(zram) = device.zram {
+///         println!("compressed {} KB into {} KB", zram.original_size, zram.compressed_size);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwapDevice {
+    /// Path of the partition, file or device.
+    pub path: PathBuf,
+    /// Kind of the swap area.
+    pub kind: SwapKind,
+    /// Size of the swap area (in KB).
+    pub size: u64,
+    /// Amount of used swap space (in KB).
+    pub used: u64,
+    /// Priority of the swap area: the areas with the highest priority are used first.
+    pub priority: i32,
+    /// Compression statistics if it is a zram device.
+    pub zram: Option<ZramStats>,
+}
+
+/// Huge pages of a given size on a NUMA node.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NodeHugePages {
+    /// Identifier of the NUMA node.
+    pub node: u32,
+    /// Number of huge pages in the pool of the node.
+    pub total: u64,
+    /// Number of huge pages of the node which aren't allocated.
+    pub free: u64,
+    /// Number of surplus huge pages of the node.
+    pub surplus: u64,
+}
+
+/// Pool of huge pages of a given size.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HugePagesPool {
+    /// Size of a huge page (in KB).
+    pub page_size: u64,
+    /// Number of huge pages in the pool.
+    pub total: u64,
+    /// Number of huge pages which aren't allocated.
+    pub free: u64,
+    /// Number of huge pages reserved for an allocation but not allocated yet.
+    pub reserved: u64,
+    /// Number of huge pages allocated above `total` (up to `overcommit`).
+    pub surplus: u64,
+    /// Maximum number of surplus huge pages.
+    pub overcommit: u64,
+    /// Huge pages of each NUMA node, sorted by node.
+    pub nodes: Vec<NodeHugePages>,
+}
+
+/// Transparent huge pages (THP) settings and khugepaged counters.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TransparentHugePages {
+    /// When huge pages are used for anonymous memory: `always`, `madvise` or `never`.
+    pub enabled: String,
+    /// When the kernel compacts the memory to allocate huge pages: `always`, `defer`,
+    /// `defer+madvise`, `madvise` or `never`.
+    pub defrag: String,
+    /// Whether khugepaged compacts the memory to collapse pages into huge pages.
+    pub khugepaged_defrag: Option<bool>,
+    /// Number of pages khugepaged scans at each pass.
+    pub khugepaged_pages_to_scan: Option<u64>,
+    /// Number of huge pages created by khugepaged.
+    pub khugepaged_pages_collapsed: Option<u64>,
+    /// Number of complete scans of the memory made by khugepaged.
+    pub khugepaged_full_scans: Option<u64>,
+}
+
+/// Huge pages pools and transparent huge pages information.
+///
+/// The memory of the huge pages pools is counted as used by
+/// [`SystemExt::used_memory`][crate::SystemExt::used_memory], even when the huge pages are free.
+///
+/// It is returned by [`SystemExt::hugepages`][crate::SystemExt::hugepages].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new();
+/// let hugepages = s.hugepages();
+/// for pool in &hugepages.pools {
+///     println!("{} KB pages: {}/{} free", pool.page_size, pool.free, pool.total);
+/// }
+/// if let Some(thp) = hugepages.transparent {
+///     println!("THP: {} (defrag: {})", thp.enabled, thp.defrag);
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HugePages {
+    /// Pools of huge pages, sorted by page size.
+    pub pools: Vec<HugePagesPool>,
+    /// Transparent huge pages information, if they are supported.
+    pub transparent: Option<TransparentHugePages>,
+}
+
+/// Memory statistics of a NUMA node.
+///
+/// It is returned by [`SystemExt::numa_nodes`][crate::SystemExt::numa_nodes].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new();
+/// for node in s.numa_nodes() {
+///     println!(
+///         "node{}: {}/{} KB used, {} NUMA misses",
+///         node.id, node.used_memory, node.total_memory, node.numa_miss,
+///     );
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct NumaNode {
+    /// Identifier of the node (`N` in `nodeN`).
+    pub id: u32,
+    /// Size of the memory of the node (in KB).
+    pub total_memory: u64,
+    /// Amount of free memory of the node (in KB).
+    pub free_memory: u64,
+    /// Amount of used memory of the node (in KB).
+    pub used_memory: u64,
+    /// Number of pages allocated on this node as intended.
+    pub numa_hit: u64,
+    /// Number of pages allocated on this node despite the process preferring another node.
+    pub numa_miss: u64,
+    /// Number of pages intended for this node but allocated on another one.
+    pub numa_foreign: u64,
+    /// Number of interleaved pages allocated on this node as intended.
+    pub interleave_hit: u64,
+    /// Number of pages allocated on this node while the process was running on it.
+    pub local_node: u64,
+    /// Number of pages allocated on this node while the process was running on another node.
+    pub other_node: u64,
+    /// Logical CPUs of the node, sorted.
+    pub cpus: Vec<usize>,
+    /// Distances to each node (in the same order as the nodes returned by
+    /// [`SystemExt::numa_nodes`][crate::SystemExt::numa_nodes]). The distance to itself is
+    /// usually 10.
+    pub distances: Vec<u32>,
+}
+
+/// Usage of the system-wide kernel limits.
+///
+/// It is returned by [`SystemExt::kernel_limits`][crate::SystemExt::kernel_limits].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new();
+/// let limits = s.kernel_limits();
+/// println!("{}/{} open files", limits.open_files, limits.max_open_files);
+/// if let (Some(entries), Some(max)) = (limits.conntrack_entries, limits.max_conntrack_entries) {
+///     println!("{}/{} conntrack entries", entries, max);
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct KernelLimits {
+    /// Number of file handles in use.
+    pub open_files: u64,
+    /// Maximum number of file handles the kernel will allocate.
+    pub max_open_files: u64,
+    /// Number of inodes allocated by the kernel.
+    pub inodes: u64,
+    /// Number of allocated inodes which are free.
+    pub free_inodes: u64,
+    /// Number of processes and threads, each of them using a PID.
+    pub pids: u64,
+    /// Value at which the PIDs wrap around, so the maximum number of PIDs in use.
+    pub max_pids: u64,
+    /// Maximum number of threads (processes included).
+    pub max_threads: u64,
+    /// Number of entries in the netfilter connection tracking table, if it is enabled.
+    pub conntrack_entries: Option<u64>,
+    /// Size of the netfilter connection tracking table, if it is enabled.
+    pub max_conntrack_entries: Option<u64>,
+}
+
 macro_rules! xid {
     ($(#[$outer:meta])+ $name:ident, $type:ty) => {
         $(#[$outer])+
@@ -464,3 +1279,1027 @@ pub struct DiskUsage {
     /// Number of read bytes since the last refresh.
     pub read_bytes: u64,
 }
+
+/// Type containing the I/O counters of a process at the system call level.
+///
+/// Unlike [`DiskUsage`], the characters read and written include the ones which didn't need
+/// any storage access, like page cache hits or pipes and sockets I/O.
+///
+/// It is returned by [`ProcessExt::io_stats`][crate::ProcessExt::io_stats].
+///
+/// ```no_run
+/// use sysinfo::{ProcessExt, System, SystemExt};
+///
+/// let s = System::new_all();
+/// for (pid, process) in s.processes() {
+///     let io_stats = process.io_stats();
+///     println!("[{}] read syscalls : new/total => {}/{}",
+///         pid,
+///         io_stats.read_syscalls,
+///         io_stats.total_read_syscalls,
+///     );
+///     println!("[{}] write syscalls: new/total => {}/{}",
+///         pid,
+///         io_stats.write_syscalls,
+///         io_stats.total_write_syscalls,
+///     );
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct IoStats {
+    /// Total number of bytes read with `read`-like system calls.
+    pub total_read_chars: u64,
+    /// Number of bytes read with `read`-like system calls since the last refresh.
+    pub read_chars: u64,
+    /// Total number of bytes written with `write`-like system calls.
+    pub total_written_chars: u64,
+    /// Number of bytes written with `write`-like system calls since the last refresh.
+    pub written_chars: u64,
+    /// Total number of `read`-like system calls.
+    pub total_read_syscalls: u64,
+    /// Number of `read`-like system calls since the last refresh.
+    pub read_syscalls: u64,
+    /// Total number of `write`-like system calls.
+    pub total_write_syscalls: u64,
+    /// Number of `write`-like system calls since the last refresh.
+    pub write_syscalls: u64,
+    /// Total number of bytes which were going to be written to storage but weren't because the
+    /// page cache was discarded (when a file is truncated for example).
+    pub total_cancelled_written_bytes: u64,
+    /// Number of cancelled written bytes since the last refresh.
+    pub cancelled_written_bytes: u64,
+}
+
+/// Type containing received and transmitted bytes and packets.
+///
+/// It is returned by [`ProcessExt::network_usage`][crate::ProcessExt::network_usage].
+///
+/// ```no_run
+/// use sysinfo::{ProcessExt, ProcessRefreshKind, System, SystemExt};
+///
+/// let mut s = System::new();
+/// s.refresh_processes_specifics(ProcessRefreshKind::everything());
+/// for (pid, process) in s.processes() {
+///     let network_usage = process.network_usage();
+///     println!("[{}] received bytes   : new/total => {}/{} B",
+///         pid,
+///         network_usage.received_bytes,
+///         network_usage.total_received_bytes,
+///     );
+///     println!("[{}] transmitted bytes: new/total => {}/{} B",
+///         pid,
+///         network_usage.transmitted_bytes,
+///         network_usage.total_transmitted_bytes,
+///     );
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct NetworkUsage {
+    /// Total number of received bytes.
+    pub total_received_bytes: u64,
+    /// Number of received bytes since the last refresh.
+    pub received_bytes: u64,
+    /// Total number of transmitted bytes.
+    pub total_transmitted_bytes: u64,
+    /// Number of transmitted bytes since the last refresh.
+    pub transmitted_bytes: u64,
+    /// Total number of received packets.
+    pub total_received_packets: u64,
+    /// Number of received packets since the last refresh.
+    pub received_packets: u64,
+    /// Total number of transmitted packets.
+    pub total_transmitted_packets: u64,
+    /// Number of transmitted packets since the last refresh.
+    pub transmitted_packets: u64,
+}
+
+/// Type containing the CPU time consumed by a process since it started.
+///
+/// It is returned by [`ProcessExt::cpu_time`][crate::ProcessExt::cpu_time].
+///
+/// ```no_run
+/// use sysinfo::{ProcessExt, System, SystemExt};
+///
+/// let s = System::new_all();
+/// for (pid, process) in s.processes() {
+///     let cpu_time = process.cpu_time();
+///     println!("[{}] user: {:?}, system: {:?}, total: {:?}",
+///         pid,
+///         cpu_time.user,
+///         cpu_time.system,
+///         cpu_time.total(),
+///     );
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CpuTime {
+    /// Time spent running in user mode.
+    pub user: Duration,
+    /// Time spent running in kernel mode.
+    pub system: Duration,
+}
+
+impl CpuTime {
+    /// Returns the total CPU time (user and system) consumed by the process.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.process(1337) {
+    ///     println!("{} CPU-seconds", process.cpu_time().total().as_secs_f64());
+    /// }
+    /// ```
+    pub fn total(&self) -> Duration {
+        self.user + self.system
+    }
+}
+
+/// Scheduling policy of a process.
+///
+/// It is part of the information returned by
+/// [`ProcessExt::scheduling`][crate::ProcessExt::scheduling].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchedulingPolicy {
+    /// Default time-sharing policy (`SCHED_OTHER`).
+    Other,
+    /// First-in first-out real-time policy (`SCHED_FIFO`).
+    Fifo,
+    /// Round-robin real-time policy (`SCHED_RR`).
+    RoundRobin,
+    /// Time-sharing policy for CPU-intensive non-interactive processes (`SCHED_BATCH`).
+    Batch,
+    /// Policy for very low priority background jobs (`SCHED_IDLE`).
+    Idle,
+    /// Deadline real-time policy (`SCHED_DEADLINE`).
+    Deadline,
+    /// Unknown policy.
+    Unknown(u32),
+}
+
+// `#[derive(Default)]` on enums isn't available with our minimum supported Rust version.
+#[allow(clippy::derivable_impls)]
+impl Default for SchedulingPolicy {
+    fn default() -> SchedulingPolicy {
+        SchedulingPolicy::Other
+    }
+}
+
+impl From<u32> for SchedulingPolicy {
+    fn from(policy: u32) -> SchedulingPolicy {
+        match policy {
+            0 => SchedulingPolicy::Other,
+            1 => SchedulingPolicy::Fifo,
+            2 => SchedulingPolicy::RoundRobin,
+            3 => SchedulingPolicy::Batch,
+            5 => SchedulingPolicy::Idle,
+            6 => SchedulingPolicy::Deadline,
+            x => SchedulingPolicy::Unknown(x),
+        }
+    }
+}
+
+/// Type containing the scheduling information of a process.
+///
+/// It is returned by [`ProcessExt::scheduling`][crate::ProcessExt::scheduling].
+///
+/// ```no_run
+/// use sysinfo::{ProcessExt, System, SystemExt};
+///
+/// let s = System::new_all();
+/// for (pid, process) in s.processes() {
+///     let scheduling = process.scheduling();
+///     println!("[{}] nice: {}, policy: {:?}, last ran on processor {}",
+///         pid,
+///         scheduling.nice,
+///         scheduling.policy,
+///         scheduling.processor,
+///     );
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SchedulingInfo {
+    /// Nice value, from 19 (lowest priority) to -20 (highest priority).
+    pub nice: i32,
+    /// Priority as seen by the kernel. For real-time processes, it is the negated real-time
+    /// priority minus one, otherwise it is the nice value plus 20.
+    pub priority: i32,
+    /// Scheduling policy.
+    pub policy: SchedulingPolicy,
+    /// Real-time priority, from 1 to 99 for real-time policies, 0 otherwise.
+    pub rt_priority: u32,
+    /// Processor on which the process last ran.
+    pub processor: u32,
+}
+
+/// Type containing the number of context switches of a process.
+///
+/// It is returned by [`ProcessExt::context_switches`][crate::ProcessExt::context_switches].
+///
+/// ```no_run
+/// use sysinfo::{ProcessExt, System, SystemExt};
+///
+/// let s = System::new_all();
+/// for (pid, process) in s.processes() {
+///     let switches = process.context_switches();
+///     println!("[{}] voluntary: new/total => {}/{}",
+///         pid,
+///         switches.voluntary,
+///         switches.total_voluntary,
+///     );
+///     println!("[{}] involuntary: new/total => {}/{}",
+///         pid,
+///         switches.involuntary,
+///         switches.total_involuntary,
+///     );
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd)]
+pub struct ContextSwitches {
+    /// Total number of voluntary context switches.
+    pub total_voluntary: u64,
+    /// Number of voluntary context switches since the last refresh.
+    pub voluntary: u64,
+    /// Total number of involuntary context switches.
+    pub total_involuntary: u64,
+    /// Number of involuntary context switches since the last refresh.
+    pub involuntary: u64,
+}
+
+/// Type containing the number of page faults of a process.
+///
+/// It is returned by [`ProcessExt::page_faults`][crate::ProcessExt::page_faults].
+///
+/// ```no_run
+/// use sysinfo::{ProcessExt, System, SystemExt};
+///
+/// let s = System::new_all();
+/// for (pid, process) in s.processes() {
+///     let faults = process.page_faults();
+///     println!("[{}] minor faults: new/total => {}/{}",
+///         pid,
+///         faults.minor,
+///         faults.total_minor,
+///     );
+///     println!("[{}] major faults: new/total => {}/{}",
+///         pid,
+///         faults.major,
+///         faults.total_major,
+///     );
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd)]
+pub struct PageFaults {
+    /// Total number of minor faults (which didn't require loading a memory page from disk).
+    pub total_minor: u64,
+    /// Number of minor faults since the last refresh.
+    pub minor: u64,
+    /// Total number of major faults (which required loading a memory page from disk).
+    pub total_major: u64,
+    /// Number of major faults since the last refresh.
+    pub major: u64,
+}
+
+/// Kind of Linux namespace.
+///
+/// It is used by [`SystemExt::processes_in_namespace`][crate::SystemExt::processes_in_namespace].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NamespaceKind {
+    /// Cgroup namespace: isolates the view of the cgroup hierarchy.
+    Cgroup,
+    /// IPC namespace: isolates System V IPC objects and POSIX message queues.
+    Ipc,
+    /// Mount namespace: isolates the mount points.
+    Mnt,
+    /// Network namespace: isolates the network devices, stacks and ports.
+    Net,
+    /// PID namespace: isolates the process IDs.
+    Pid,
+    /// User namespace: isolates the user and group IDs.
+    User,
+    /// UTS namespace: isolates the hostname and the NIS domain name.
+    Uts,
+}
+
+/// Identifiers (inode numbers) of the Linux namespaces a process belongs to.
+///
+/// Two processes are in the same namespace if they have the same identifier for it. A
+/// namespace is `None` if it couldn't be retrieved (because of missing permissions for example)
+/// or isn't supported.
+///
+/// It is returned by [`ProcessExt::namespaces`][crate::ProcessExt::namespaces].
+///
+/// ```no_run
+/// use sysinfo::{ProcessExt, ProcessRefreshKind, System, SystemExt};
+///
+/// let mut s = System::new();
+/// s.refresh_processes_specifics(ProcessRefreshKind::new().with_namespaces());
+/// for (pid, process) in s.processes() {
+///     println!("[{}] pid namespace: {:?}", pid, process.namespaces().pid);
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Namespaces {
+    /// Cgroup namespace.
+    pub cgroup: Option<u64>,
+    /// IPC namespace.
+    pub ipc: Option<u64>,
+    /// Mount namespace.
+    pub mnt: Option<u64>,
+    /// Network namespace.
+    pub net: Option<u64>,
+    /// PID namespace.
+    pub pid: Option<u64>,
+    /// User namespace.
+    pub user: Option<u64>,
+    /// UTS namespace.
+    pub uts: Option<u64>,
+}
+
+impl Namespaces {
+    /// Returns the identifier of the namespace of the given `kind`.
+    ///
+    /// ```
+    /// use sysinfo::{NamespaceKind, Namespaces};
+    ///
+    /// let namespaces = Namespaces {
+    ///     net: Some(4026531840),
+    ///     ..Namespaces::default()
+    /// };
+    /// assert_eq!(namespaces.get(NamespaceKind::Net), Some(4026531840));
+    /// assert_eq!(namespaces.get(NamespaceKind::Pid), None);
+    /// ```
+    pub fn get(&self, kind: NamespaceKind) -> Option<u64> {
+        match kind {
+            NamespaceKind::Cgroup => self.cgroup,
+            NamespaceKind::Ipc => self.ipc,
+            NamespaceKind::Mnt => self.mnt,
+            NamespaceKind::Net => self.net,
+            NamespaceKind::Pid => self.pid,
+            NamespaceKind::User => self.user,
+            NamespaceKind::Uts => self.uts,
+        }
+    }
+}
+
+/// Container runtime which created a [`Container`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContainerRuntime {
+    /// Docker.
+    Docker,
+    /// containerd (through its CRI plugin).
+    Containerd,
+    /// CRI-O.
+    CriO,
+    /// Podman.
+    Podman,
+    /// The runtime couldn't be determined (a Kubernetes container using the `cgroupfs` driver
+    /// for example).
+    Unknown,
+}
+
+/// Container a process belongs to, as detected from its cgroup path.
+///
+/// It is returned by [`ProcessExt::container`][crate::ProcessExt::container].
+///
+/// ```no_run
+/// use sysinfo::{ProcessExt, System, SystemExt};
+///
+/// let s = System::new_all();
+/// for (pid, process) in s.processes() {
+///     if let Some(container) = process.container() {
+///         println!("[{}] {:?} container {}", pid, container.runtime, container.id);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Container {
+    /// Full identifier of the container (64 hexadecimal characters).
+    pub id: String,
+    /// Runtime which created the container.
+    pub runtime: ContainerRuntime,
+    /// UID of the Kubernetes pod the container belongs to, if any.
+    pub pod_uid: Option<String>,
+}
+
+/// Resources used by the processes of a systemd unit.
+///
+/// It is returned by [`SystemExt::systemd_units_usage`][crate::SystemExt::systemd_units_usage].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new_all();
+/// for (unit, usage) in s.systemd_units_usage() {
+///     println!("{}: {}% CPU, {} KB", unit, usage.cpu_usage, usage.memory);
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SystemdUnitUsage {
+    /// Pids of the processes of the unit, sorted.
+    pub processes: Vec<Pid>,
+    /// Sum of the CPU usage of the processes (see
+    /// [`ProcessExt::cpu_usage`][crate::ProcessExt::cpu_usage]).
+    pub cpu_usage: f32,
+    /// Sum of the memory usage of the processes (in KB). The memory shared between them is
+    /// counted several times.
+    pub memory: u64,
+    /// Sum of the virtual memory usage of the processes (in KB).
+    pub virtual_memory: u64,
+    /// Sum of the disk usage of the processes.
+    pub disk_usage: DiskUsage,
+}
+
+/// Pattern used by [`ProcessFilter`] to match strings.
+///
+/// ```
+/// use sysinfo::Pattern;
+///
+/// assert!(Pattern::exact("bash").is_match("bash"));
+/// assert!(Pattern::contains("ash").is_match("bash"));
+/// assert!(Pattern::glob("*sh").is_match("bash"));
+/// assert!(Pattern::glob("[bz]sh").is_match("zsh"));
+/// assert!(!Pattern::glob("b?sh").is_match("bsh"));
+/// ```
+///
+/// Regular expressions are supported with `Pattern::regex` when the `regex` cargo feature is
+/// enabled.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum Pattern {
+    /// Matches strings equal to the given one.
+    Exact(String),
+    /// Matches strings containing the given one.
+    Contains(String),
+    /// Matches strings using a glob pattern:
+    ///
+    ///  * `*` matches any sequence of characters (including an empty one).
+    ///  * `?` matches any single character.
+    ///  * `[abc]` and `[a-z]` match any character in the set (`[!abc]` or `[^abc]` match any
+    ///    character which isn't in the set).
+    ///  * `\` escapes the next character.
+    Glob(String),
+    /// Matches strings using a regular expression. It isn't anchored: use `^` and `$` to match
+    /// the whole string.
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Pattern) -> bool {
+        match (self, other) {
+            (Pattern::Exact(a), Pattern::Exact(b))
+            | (Pattern::Contains(a), Pattern::Contains(b))
+            | (Pattern::Glob(a), Pattern::Glob(b)) => a == b,
+            #[cfg(feature = "regex")]
+            (Pattern::Regex(a), Pattern::Regex(b)) => a.as_str() == b.as_str(),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Pattern {}
+
+impl Pattern {
+    /// Creates a [`Pattern::Exact`].
+    pub fn exact<S: Into<String>>(s: S) -> Pattern {
+        Pattern::Exact(s.into())
+    }
+
+    /// Creates a [`Pattern::Contains`].
+    pub fn contains<S: Into<String>>(s: S) -> Pattern {
+        Pattern::Contains(s.into())
+    }
+
+    /// Creates a [`Pattern::Glob`].
+    pub fn glob<S: Into<String>>(s: S) -> Pattern {
+        Pattern::Glob(s.into())
+    }
+
+    /// Creates a [`Pattern::Regex`], or returns an error if `s` isn't a valid regular
+    /// expression.
+    #[cfg(feature = "regex")]
+    pub fn regex(s: &str) -> Result<Pattern, regex::Error> {
+        regex::Regex::new(s).map(Pattern::Regex)
+    }
+
+    /// Returns `true` if `s` matches this pattern.
+    pub fn is_match(&self, s: &str) -> bool {
+        match self {
+            Pattern::Exact(p) => p == s,
+            Pattern::Contains(p) => s.contains(p.as_str()),
+            Pattern::Glob(p) => {
+                let pattern = p.chars().collect::<Vec<_>>();
+                let text = s.chars().collect::<Vec<_>>();
+                glob_match(&pattern, &text)
+            }
+            #[cfg(feature = "regex")]
+            Pattern::Regex(r) => r.is_match(s),
+        }
+    }
+}
+
+fn glob_match(pattern: &[char], text: &[char]) -> bool {
+    let mut p = 0;
+    let mut t = 0;
+    // Position of the last `*` in the pattern and of the text character it is currently
+    // matching up to, used to backtrack.
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() {
+            match pattern[p] {
+                '*' => {
+                    star = Some((p, t));
+                    p += 1;
+                    continue;
+                }
+                '?' => {
+                    p += 1;
+                    t += 1;
+                    continue;
+                }
+                '[' => {
+                    if let Some((matched, next)) = glob_match_class(pattern, p, text[t]) {
+                        if matched {
+                            p = next;
+                            t += 1;
+                            continue;
+                        }
+                    } else if text[t] == '[' {
+                        // Not a valid class so it's a literal `[`.
+                        p += 1;
+                        t += 1;
+                        continue;
+                    }
+                }
+                '\\' if p + 1 < pattern.len() => {
+                    if pattern[p + 1] == text[t] {
+                        p += 2;
+                        t += 1;
+                        continue;
+                    }
+                }
+                c => {
+                    if c == text[t] {
+                        p += 1;
+                        t += 1;
+                        continue;
+                    }
+                }
+            }
+        }
+        match star {
+            Some((star_p, star_t)) => {
+                p = star_p + 1;
+                t = star_t + 1;
+                star = Some((star_p, star_t + 1));
+            }
+            None => return false,
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Matches `c` against the class starting at `pattern[start]` (which is `[`). Returns whether it
+/// matched and the position following the class, or `None` if the class isn't terminated.
+fn glob_match_class(pattern: &[char], start: usize, c: char) -> Option<(bool, usize)> {
+    let mut i = start + 1;
+    let negated = matches!(pattern.get(i), Some('!') | Some('^'));
+    if negated {
+        i += 1;
+    }
+    let mut matched = false;
+    let mut first = true;
+    loop {
+        let current = *pattern.get(i)?;
+        if current == ']' && !first {
+            return Some((matched != negated, i + 1));
+        }
+        first = false;
+        match (pattern.get(i + 1), pattern.get(i + 2)) {
+            (Some('-'), Some(&end)) if end != ']' => {
+                matched |= current <= c && c <= end;
+                i += 3;
+            }
+            _ => {
+                matched |= current == c;
+                i += 1;
+            }
+        }
+    }
+}
+
+enum FilterCondition {
+    Name(Pattern),
+    Exe(Pattern),
+    Cmd(Pattern),
+    Environ(String, Pattern),
+    User(Uid),
+    Parent(Pid),
+    Status(ProcessStatus),
+    Cgroup(Pattern),
+    MinCpuUsage(f32),
+    MaxCpuUsage(f32),
+    MinMemory(u64),
+    MaxMemory(u64),
+    Predicate(Box<dyn Fn(&Process) -> bool + Send + Sync>),
+}
+
+impl FilterCondition {
+    fn is_match(&self, process: &Process) -> bool {
+        match self {
+            FilterCondition::Name(p) => p.is_match(process.name()),
+            FilterCondition::Exe(p) => p.is_match(&process.exe().to_string_lossy()),
+            FilterCondition::Cmd(p) => {
+                let cmd = process
+                    .cmd_os()
+                    .iter()
+                    .map(|arg| arg.to_string_lossy())
+                    .collect::<Vec<_>>();
+                p.is_match(&cmd.join(" "))
+            }
+            FilterCondition::Environ(key, p) => process
+                .environ_os()
+                .iter()
+                .filter_map(|var| crate::utils::split_env_var(var))
+                .find(|(k, _)| k.to_string_lossy() == key.as_str())
+                .map(|(_, v)| p.is_match(&v.to_string_lossy()))
+                .unwrap_or(false),
+            FilterCondition::User(uid) => process.user_id() == Some(*uid),
+            FilterCondition::Parent(pid) => process.parent() == Some(*pid),
+            FilterCondition::Status(status) => process.status() == *status,
+            FilterCondition::Cgroup(p) => process.cgroups().iter().any(|cgroup| p.is_match(cgroup)),
+            FilterCondition::MinCpuUsage(min) => process.cpu_usage() >= *min,
+            FilterCondition::MaxCpuUsage(max) => process.cpu_usage() <= *max,
+            FilterCondition::MinMemory(min) => process.memory() >= *min,
+            FilterCondition::MaxMemory(max) => process.memory() <= *max,
+            FilterCondition::Predicate(f) => f(process),
+        }
+    }
+}
+
+/// Used to select processes matching some conditions. A process matches the filter if it
+/// matches all its conditions.
+///
+/// ```no_run
+/// use sysinfo::{Pattern, ProcessExt, ProcessFilter, System, SystemExt};
+///
+/// let s = System::new_all();
+/// let filter = ProcessFilter::new()
+///     .with_name(Pattern::glob("java*"))
+///     .with_cmd(Pattern::contains("-jar"))
+///     .with_min_memory(100_000);
+/// for process in s.processes_matching(&filter) {
+///     println!("{} {}", process.pid(), process.name());
+/// }
+/// ```
+#[derive(Default)]
+pub struct ProcessFilter {
+    conditions: Vec<FilterCondition>,
+}
+
+impl fmt::Debug for ProcessFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ProcessFilter")
+            .field("conditions", &self.conditions.len())
+            .finish()
+    }
+}
+
+impl ProcessFilter {
+    /// Creates a new `ProcessFilter` without any condition: it matches all processes.
+    pub fn new() -> ProcessFilter {
+        ProcessFilter::default()
+    }
+
+    fn with(mut self, condition: FilterCondition) -> ProcessFilter {
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Only matches processes whose [name][crate::ProcessExt::name] matches `pattern`.
+    pub fn with_name(self, pattern: Pattern) -> ProcessFilter {
+        self.with(FilterCondition::Name(pattern))
+    }
+
+    /// Only matches processes whose [executable path][crate::ProcessExt::exe] matches `pattern`.
+    pub fn with_exe(self, pattern: Pattern) -> ProcessFilter {
+        self.with(FilterCondition::Exe(pattern))
+    }
+
+    /// Only matches processes whose [command line][crate::ProcessExt::cmd_os] matches
+    /// `pattern`. The arguments are joined with spaces before being matched.
+    pub fn with_cmd(self, pattern: Pattern) -> ProcessFilter {
+        self.with(FilterCondition::Cmd(pattern))
+    }
+
+    /// Only matches processes having the environment variable `key` with a value matching
+    /// `pattern`.
+    pub fn with_environ<S: Into<String>>(self, key: S, pattern: Pattern) -> ProcessFilter {
+        self.with(FilterCondition::Environ(key.into(), pattern))
+    }
+
+    /// Only matches processes owned by the given user.
+    pub fn with_user(self, uid: Uid) -> ProcessFilter {
+        self.with(FilterCondition::User(uid))
+    }
+
+    /// Only matches processes whose parent is `pid`.
+    pub fn with_parent(self, pid: Pid) -> ProcessFilter {
+        self.with(FilterCondition::Parent(pid))
+    }
+
+    /// Only matches processes with the given status.
+    pub fn with_status(self, status: ProcessStatus) -> ProcessFilter {
+        self.with(FilterCondition::Status(status))
+    }
+
+    /// Only matches processes belonging to a cgroup whose [path][crate::ProcessExt::cgroups]
+    /// matches `pattern`. The cgroups are only retrieved if [`ProcessRefreshKind::cgroup`] is
+    /// set.
+    ///
+    /// ⚠️ Only implemented on Linux and Android, it never matches otherwise.
+    pub fn with_cgroup(self, pattern: Pattern) -> ProcessFilter {
+        self.with(FilterCondition::Cgroup(pattern))
+    }
+
+    /// Only matches processes with a [CPU usage][crate::ProcessExt::cpu_usage] of at least
+    /// `cpu_usage` %.
+    pub fn with_min_cpu_usage(self, cpu_usage: f32) -> ProcessFilter {
+        self.with(FilterCondition::MinCpuUsage(cpu_usage))
+    }
+
+    /// Only matches processes with a [CPU usage][crate::ProcessExt::cpu_usage] of at most
+    /// `cpu_usage` %.
+    pub fn with_max_cpu_usage(self, cpu_usage: f32) -> ProcessFilter {
+        self.with(FilterCondition::MaxCpuUsage(cpu_usage))
+    }
+
+    /// Only matches processes using at least `memory` KB of [memory][crate::ProcessExt::memory].
+    pub fn with_min_memory(self, memory: u64) -> ProcessFilter {
+        self.with(FilterCondition::MinMemory(memory))
+    }
+
+    /// Only matches processes using at most `memory` KB of [memory][crate::ProcessExt::memory].
+    pub fn with_max_memory(self, memory: u64) -> ProcessFilter {
+        self.with(FilterCondition::MaxMemory(memory))
+    }
+
+    /// Only matches processes for which `predicate` returns `true`.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, ProcessFilter, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// let filter = ProcessFilter::new().with_predicate(|p| p.name().ends_with("d"));
+    /// for process in s.processes_matching(&filter) {
+    ///     println!("{} {}", process.pid(), process.name());
+    /// }
+    /// ```
+    pub fn with_predicate<F>(self, predicate: F) -> ProcessFilter
+    where
+        F: Fn(&Process) -> bool + Send + Sync + 'static,
+    {
+        self.with(FilterCondition::Predicate(Box::new(predicate)))
+    }
+
+    /// Returns `true` if `process` matches all the conditions of this filter.
+    pub fn is_match(&self, process: &Process) -> bool {
+        self.conditions.iter().all(|c| c.is_match(process))
+    }
+
+    /// Returns what needs to be refreshed to check the conditions of this filter.
+    pub(crate) fn refresh_kind(&self) -> ProcessRefreshKind {
+        self.conditions.iter().fold(
+            ProcessRefreshKind::new(),
+            |kind, condition| match condition {
+                FilterCondition::Cmd(_) => kind.with_cmd(),
+                FilterCondition::Environ(..) => kind.with_environ(),
+                FilterCondition::Cgroup(_) => kind.with_cgroup(),
+                _ => kind,
+            },
+        )
+    }
+}
+
+/// Iterator over the processes matching a [`ProcessFilter`].
+///
+/// It is returned by [`SystemExt::processes_matching`][crate::SystemExt::processes_matching].
+///
+/// ```no_run
+/// use sysinfo::{Pattern, ProcessFilter, System, SystemExt};
+///
+/// let s = System::new_all();
+/// let filter = ProcessFilter::new().with_name(Pattern::exact("bash"));
+/// let nb_bash = s.processes_matching(&filter).count();
+/// ```
+pub struct ProcessesMatching<'a> {
+    inner: std::collections::hash_map::Values<'a, Pid, Process>,
+    filter: &'a ProcessFilter,
+}
+
+impl<'a> ProcessesMatching<'a> {
+    pub(crate) fn new(
+        inner: std::collections::hash_map::Values<'a, Pid, Process>,
+        filter: &'a ProcessFilter,
+    ) -> Self {
+        ProcessesMatching { inner, filter }
+    }
+}
+
+impl<'a> Iterator for ProcessesMatching<'a> {
+    type Item = &'a Process;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let filter = self.filter;
+        self.inner.find(|p| filter.is_match(p))
+    }
+}
+
+/// Last known information about a process which exited.
+///
+/// It is part of [`ProcessDiff`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExitedProcess {
+    /// Pid of the process.
+    pub pid: Pid,
+    /// Name of the process.
+    pub name: String,
+    /// Command line of the process.
+    pub cmd: Vec<String>,
+    /// Path of the executable of the process.
+    pub exe: PathBuf,
+    /// Time of process launch (see [`ProcessExt::start_time_precise`]).
+    pub start_time: SystemTime,
+    /// CPU time consumed by the process as of the last refresh before it exited.
+    pub cpu_time: CpuTime,
+}
+
+impl ExitedProcess {
+    fn new(process: &Process) -> ExitedProcess {
+        ExitedProcess {
+            pid: process.pid(),
+            name: process.name().to_owned(),
+            cmd: process.cmd().to_vec(),
+            exe: process.exe().to_path_buf(),
+            start_time: process.start_time_precise(),
+            cpu_time: process.cpu_time(),
+        }
+    }
+}
+
+/// Previous information about a process whose name, executable or command line changed, which
+/// usually means that it executed another program.
+///
+/// It is part of [`ProcessDiff`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChangedProcess {
+    /// Pid of the process.
+    pub pid: Pid,
+    /// Name of the process before the change.
+    pub previous_name: String,
+    /// Command line of the process before the change.
+    pub previous_cmd: Vec<String>,
+    /// Path of the executable of the process before the change.
+    pub previous_exe: PathBuf,
+}
+
+/// Differences in the list of processes between two refreshes.
+///
+/// It is returned by [`SystemExt::refresh_processes_diff`][crate::SystemExt::refresh_processes_diff].
+///
+/// If a pid got reused by a new process between the two refreshes, it appears both in `exited`
+/// and in `spawned`.
+///
+/// ```no_run
+/// use sysinfo::{ProcessRefreshKind, System, SystemExt};
+///
+/// let mut s = System::new_all();
+/// // Later on...
+/// let diff = s.refresh_processes_diff(ProcessRefreshKind::everything());
+/// for pid in &diff.spawned {
+///     println!("new process: {}", pid);
+/// }
+/// for exited in &diff.exited {
+///     println!("exited process: {} {:?}", exited.pid, exited.cmd);
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProcessDiff {
+    /// Pids of the processes which appeared.
+    pub spawned: Vec<Pid>,
+    /// Processes which exited.
+    pub exited: Vec<ExitedProcess>,
+    /// Processes whose name, executable or command line changed.
+    pub changed: Vec<ChangedProcess>,
+}
+
+impl ProcessDiff {
+    pub(crate) fn snapshot(processes: &HashMap<Pid, Process>) -> HashMap<Pid, ExitedProcess> {
+        processes
+            .iter()
+            .map(|(&pid, process)| (pid, ExitedProcess::new(process)))
+            .collect()
+    }
+
+    pub(crate) fn compute(
+        mut before: HashMap<Pid, ExitedProcess>,
+        processes: &HashMap<Pid, Process>,
+    ) -> ProcessDiff {
+        let mut diff = ProcessDiff::default();
+        for (&pid, process) in processes {
+            match before.remove(&pid) {
+                Some(old) if old.start_time != process.start_time_precise() => {
+                    diff.exited.push(old);
+                    diff.spawned.push(pid);
+                }
+                Some(old) => {
+                    if old.name != process.name()
+                        || old.exe != process.exe()
+                        || old.cmd != process.cmd()
+                    {
+                        diff.changed.push(ChangedProcess {
+                            pid,
+                            previous_name: old.name,
+                            previous_cmd: old.cmd,
+                            previous_exe: old.exe,
+                        });
+                    }
+                }
+                None => diff.spawned.push(pid),
+            }
+        }
+        diff.exited.extend(before.into_values());
+        diff.spawned.sort_unstable();
+        diff.exited.sort_unstable_by_key(|p| p.pid);
+        diff.changed.sort_unstable_by_key(|p| p.pid);
+        diff
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Pattern, ProcessFilter, ProcessRefreshKind};
+
+    #[test]
+    fn check_glob() {
+        let glob = |p: &str, s: &str| Pattern::glob(p).is_match(s);
+
+        assert!(glob("", ""));
+        assert!(!glob("", "a"));
+        assert!(glob("*", ""));
+        assert!(glob("*", "anything"));
+        assert!(glob("java", "java"));
+        assert!(!glob("java", "javac"));
+        assert!(glob("java*", "javac"));
+        assert!(glob("*java*", "openjdk-java-17"));
+        assert!(glob("a*b*c", "aXbYbZc"));
+        assert!(!glob("a*b*c", "aXbYbZ"));
+        assert!(glob("?sh", "zsh"));
+        assert!(!glob("?sh", "sh"));
+        assert!(glob("[bz]sh", "bsh"));
+        assert!(!glob("[bz]sh", "csh"));
+        assert!(glob("[!bz]sh", "csh"));
+        assert!(glob("[^bz]sh", "csh"));
+        assert!(glob("python3.[0-9]", "python3.8"));
+        assert!(!glob("python3.[0-7]", "python3.8"));
+        assert!(glob("[]]", "]"));
+        assert!(glob("[a-]", "-"));
+        assert!(glob("[", "["));
+        assert!(glob("a[b", "a[b"));
+        assert!(glob("\\*", "*"));
+        assert!(!glob("\\*", "a"));
+        assert!(glob("é*", "éèà"));
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn check_regex() {
+        let pattern = Pattern::regex(r"^python3?\.\d+$").expect("invalid regex");
+        assert!(pattern.is_match("python3.9"));
+        assert!(pattern.is_match("python3.10"));
+        assert!(!pattern.is_match("python3.9-config"));
+        assert!(Pattern::regex("java").unwrap().is_match("openjdk-java-17"));
+        assert_eq!(Pattern::regex("a+").unwrap(), Pattern::regex("a+").unwrap());
+        assert_ne!(Pattern::regex("a+").unwrap(), Pattern::glob("a+"));
+        assert!(Pattern::regex("(").is_err());
+    }
+
+    #[test]
+    fn check_filter_refresh_kind() {
+        assert_eq!(
+            ProcessFilter::new()
+                .with_name(Pattern::exact("bash"))
+                .refresh_kind(),
+            ProcessRefreshKind::new()
+        );
+        assert_eq!(
+            ProcessFilter::new()
+                .with_cmd(Pattern::contains("-jar"))
+                .with_cgroup(Pattern::glob("*docker*"))
+                .refresh_kind(),
+            ProcessRefreshKind::new().with_cmd().with_cgroup()
+        );
+    }
+}
@@ -62,9 +62,18 @@ cfg_if::cfg_if! {
 }
 
 pub use common::{
-    AsU32, DiskType, DiskUsage, Gid, LoadAvg, NetworksIter, Pid, RefreshKind, Signal, Uid, User,
+    AsU32, CacheType, ChangedProcess, Container, ContainerRuntime, ContextSwitches, CpuCache,
+    CpuInfo, CpuTime, CpuTopology, CpuVulnerability, DiskType, DiskUsage, ExitedProcess,
+    FrequencyScaling, FrequencyState, Gid, HugePages, HugePagesPool, Interrupt, IoStats,
+    KernelLimits, KernelStats, LoadAvg, LogicalCpu, NamespaceKind, Namespaces, NetworkUsage,
+    NetworksIter, NodeHugePages, NumaNode, PageFaults, Pattern, Pid, Pressure, PressureStats,
+    ProcessDiff, ProcessFilter, ProcessRefreshKind, ProcessesMatching, RefreshKind,
+    ResourcePressure, SchedulingInfo, SchedulingPolicy, Signal, SwapDevice, SwapKind,
+    SystemdUnitUsage, TransparentHugePages, Uid, User, ZramStats,
 };
 pub use sys::{Component, Disk, NetworkData, Networks, Process, ProcessStatus, Processor, System};
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub use sys::{ProcessEvent, ProcessWatcher};
 pub use traits::{
     ComponentExt, DiskExt, NetworkExt, NetworksExt, ProcessExt, ProcessorExt, SystemExt, UserExt,
 };
@@ -0,0 +1,320 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::{Container, ContainerRuntime};
+
+/// Returns the cgroup paths listed in a `/proc/[pid]/cgroup` file (one per hierarchy).
+pub(crate) fn parse_cgroup_paths(data: &str) -> Vec<&str> {
+    data.lines()
+        .filter_map(|line| line.splitn(3, ':').nth(2))
+        .collect()
+}
+
+/// Returns the path of the cgroup managed by systemd: the one of the unified hierarchy (cgroup v2
+/// or hybrid mode) or of the `name=systemd` one (cgroup v1).
+pub(crate) fn parse_systemd_cgroup_path(data: &str) -> Option<&str> {
+    let mut systemd = None;
+    for line in data.lines() {
+        let mut parts = line.splitn(3, ':').skip(1);
+        match (parts.next(), parts.next()) {
+            (Some(""), Some(path)) => return Some(path),
+            (Some("name=systemd"), Some(path)) => systemd = Some(path),
+            _ => {}
+        }
+    }
+    systemd
+}
+
+/// Returns the systemd unit and slice from the path of a cgroup managed by systemd.
+///
+/// The unit is the innermost service or scope (so for a user service, it is the service and not
+/// `user@<uid>.service`) and the slice is the innermost slice containing it.
+pub(crate) fn get_systemd_unit_and_slice(path: &str) -> (Option<&str>, Option<&str>) {
+    let mut unit = None;
+    let mut slice = None;
+    for component in path.split('/') {
+        if component.ends_with(".service") || component.ends_with(".scope") {
+            unit = Some(component);
+        } else if component.ends_with(".slice") {
+            // A slice can be inside a unit, like in `user@<uid>.service/app.slice/<unit>`.
+            unit = None;
+            slice = Some(component);
+        }
+    }
+    (unit, slice)
+}
+
+/// Looks for a container in the given cgroup paths.
+///
+/// The recognized layouts are:
+///
+/// * systemd driver: `docker-<id>.scope`, `cri-containerd-<id>.scope`, `crio-<id>.scope` and
+///   `libpod-<id>.scope` (the last component can be followed by sub-cgroups created inside the
+///   container).
+/// * cgroupfs driver: `/docker/<id>` and `/kubepods/<qos>/pod<uid>/<id>`.
+///
+/// The pod UID is retrieved from the `pod<uid>` component, or from the
+/// `kubepods-<qos>-pod<uid>.slice` one with the systemd driver.
+pub(crate) fn get_container(paths: &[&str]) -> Option<Container> {
+    paths.iter().find_map(|path| get_container_from_path(path))
+}
+
+fn get_container_from_path(path: &str) -> Option<Container> {
+    let components = path
+        .split('/')
+        .filter(|c| !c.is_empty())
+        .collect::<Vec<_>>();
+    // We start from the end to ignore the sub-cgroups of the container.
+    let (index, id, runtime) = components.iter().enumerate().rev().find_map(|(i, c)| {
+        let (id, runtime) = get_container_id(c, &components[..i])?;
+        Some((i, id, runtime))
+    })?;
+    Some(Container {
+        id: id.to_owned(),
+        runtime,
+        pod_uid: components[..index]
+            .iter()
+            .rev()
+            .find_map(|c| get_pod_uid(c)),
+    })
+}
+
+fn get_container_id<'a>(
+    component: &'a str,
+    parents: &[&str],
+) -> Option<(&'a str, ContainerRuntime)> {
+    let name = component.strip_suffix(".scope").unwrap_or(component);
+    let prefixes = [
+        ("docker-", ContainerRuntime::Docker),
+        ("cri-containerd-", ContainerRuntime::Containerd),
+        ("crio-", ContainerRuntime::CriO),
+        ("libpod-", ContainerRuntime::Podman),
+    ];
+    for (prefix, runtime) in prefixes.iter() {
+        if let Some(id) = name.strip_prefix(prefix) {
+            // The monitoring processes (`crio-conmon-<id>` and `libpod-conmon-<id>`) are
+            // rejected here since they aren't in the container.
+            return if is_container_id(id) {
+                Some((id, *runtime))
+            } else {
+                None
+            };
+        }
+    }
+    if !is_container_id(name) {
+        return None;
+    }
+    match parents.first() {
+        Some(&"docker") => Some((name, ContainerRuntime::Docker)),
+        Some(&"kubepods") => Some((name, ContainerRuntime::Unknown)),
+        _ => None,
+    }
+}
+
+fn is_container_id(id: &str) -> bool {
+    id.len() == 64 && id.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+fn get_pod_uid(component: &str) -> Option<String> {
+    if let Some(uid) = component.strip_prefix("pod") {
+        // cgroupfs driver.
+        return if is_pod_uid(uid) {
+            Some(uid.to_owned())
+        } else {
+            None
+        };
+    }
+    // systemd driver: `kubepods-pod<uid>.slice` or `kubepods-<qos>-pod<uid>.slice`, with the
+    // dashes of the UID replaced by underscores.
+    let name = component
+        .strip_prefix("kubepods-")?
+        .strip_suffix(".slice")?;
+    let uid = name
+        .rsplit('-')
+        .next()?
+        .strip_prefix("pod")?
+        .replace('_', "-");
+    if is_pod_uid(&uid) {
+        Some(uid)
+    } else {
+        None
+    }
+}
+
+fn is_pod_uid(uid: &str) -> bool {
+    uid.len() == 36 && uid.bytes().all(|b| b == b'-' || b.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        get_container, get_systemd_unit_and_slice, parse_cgroup_paths, parse_systemd_cgroup_path,
+    };
+    use crate::{Container, ContainerRuntime};
+
+    const ID: &str = "9a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f708192a3b4c5d6e7f809";
+    const POD_UID: &str = "0f1e2d3c-4b5a-6978-8796-a5b4c3d2e1f0";
+
+    fn container(data: &str) -> Option<Container> {
+        get_container(&parse_cgroup_paths(data))
+    }
+
+    fn expected(runtime: ContainerRuntime, pod_uid: bool) -> Option<Container> {
+        Some(Container {
+            id: ID.to_owned(),
+            runtime,
+            pod_uid: if pod_uid {
+                Some(POD_UID.to_owned())
+            } else {
+                None
+            },
+        })
+    }
+
+    #[test]
+    fn check_docker() {
+        // cgroup v2 with the systemd driver.
+        assert_eq!(
+            container(&format!("0::/system.slice/docker-{}.scope\n", ID)),
+            expected(ContainerRuntime::Docker, false),
+        );
+        // cgroup v1 with the cgroupfs driver.
+        assert_eq!(
+            container(&format!(
+                "12:pids:/docker/{id}\n11:memory:/docker/{id}\n0::/system.slice/docker.service\n",
+                id = ID
+            )),
+            expected(ContainerRuntime::Docker, false),
+        );
+    }
+
+    #[test]
+    fn check_kubernetes() {
+        let pod = POD_UID.replace('-', "_");
+        assert_eq!(
+            container(&format!(
+                "0::/kubepods.slice/kubepods-burstable.slice/kubepods-burstable-pod{}.slice/\
+                 cri-containerd-{}.scope\n",
+                pod, ID
+            )),
+            expected(ContainerRuntime::Containerd, true),
+        );
+        // Guaranteed pods don't have a QoS slice.
+        assert_eq!(
+            container(&format!(
+                "0::/kubepods.slice/kubepods-pod{}.slice/crio-{}.scope\n",
+                pod, ID
+            )),
+            expected(ContainerRuntime::CriO, true),
+        );
+        assert_eq!(
+            container(&format!(
+                "4:cpu,cpuacct:/kubepods/besteffort/pod{}/{}\n",
+                POD_UID, ID
+            )),
+            expected(ContainerRuntime::Unknown, true),
+        );
+    }
+
+    #[test]
+    fn check_podman() {
+        assert_eq!(
+            container(&format!(
+                "0::/machine.slice/libpod-{}.scope/container\n",
+                ID
+            )),
+            expected(ContainerRuntime::Podman, false),
+        );
+        // Rootless.
+        assert_eq!(
+            container(&format!(
+                "0::/user.slice/user-1000.slice/user@1000.service/user.slice/libpod-{}.scope\n",
+                ID
+            )),
+            expected(ContainerRuntime::Podman, false),
+        );
+        // conmon isn't in the container.
+        assert_eq!(
+            container(&format!("0::/machine.slice/libpod-conmon-{}.scope\n", ID)),
+            None,
+        );
+    }
+
+    #[test]
+    fn check_no_container() {
+        assert_eq!(container(""), None);
+        assert_eq!(container("0::/\n"), None);
+        assert_eq!(
+            container("0::/user.slice/user-1000.slice/session-2.scope\n"),
+            None
+        );
+        assert_eq!(container("0::/system.slice/docker.service\n"), None);
+        // Not a full identifier.
+        assert_eq!(container("0::/system.slice/docker-abc.scope\n"), None);
+        assert_eq!(container(&format!("0::/system.slice/{}\n", ID)), None);
+    }
+
+    #[test]
+    fn check_systemd_cgroup_path() {
+        assert_eq!(
+            parse_systemd_cgroup_path("0::/system.slice/nginx.service\n"),
+            Some("/system.slice/nginx.service")
+        );
+        // Hybrid mode.
+        assert_eq!(
+            parse_systemd_cgroup_path(
+                "4:memory:/system.slice/nginx.service\n\
+                 1:name=systemd:/system.slice/nginx.service\n\
+                 0::/system.slice/nginx.service\n"
+            ),
+            Some("/system.slice/nginx.service")
+        );
+        // cgroup v1.
+        assert_eq!(
+            parse_systemd_cgroup_path(
+                "4:memory:/system.slice\n1:name=systemd:/system.slice/nginx.service\n"
+            ),
+            Some("/system.slice/nginx.service")
+        );
+        assert_eq!(parse_systemd_cgroup_path("4:memory:/system.slice\n"), None);
+    }
+
+    #[test]
+    fn check_systemd_unit_and_slice() {
+        assert_eq!(
+            get_systemd_unit_and_slice("/system.slice/nginx.service"),
+            (Some("nginx.service"), Some("system.slice"))
+        );
+        assert_eq!(
+            get_systemd_unit_and_slice("/system.slice/system-getty.slice/getty@tty1.service"),
+            (Some("getty@tty1.service"), Some("system-getty.slice"))
+        );
+        // Sub-cgroups created by the service itself.
+        assert_eq!(
+            get_systemd_unit_and_slice("/system.slice/containerd.service/sub/group"),
+            (Some("containerd.service"), Some("system.slice"))
+        );
+        assert_eq!(
+            get_systemd_unit_and_slice(
+                "/user.slice/user-1000.slice/user@1000.service/app.slice/app-firefox.scope"
+            ),
+            (Some("app-firefox.scope"), Some("app.slice"))
+        );
+        assert_eq!(
+            get_systemd_unit_and_slice("/user.slice/user-1000.slice/session-2.scope"),
+            (Some("session-2.scope"), Some("user-1000.slice"))
+        );
+        assert_eq!(
+            get_systemd_unit_and_slice("/init.scope"),
+            (Some("init.scope"), None)
+        );
+        assert_eq!(
+            get_systemd_unit_and_slice("/user.slice"),
+            (None, Some("user.slice"))
+        );
+        assert_eq!(get_systemd_unit_and_slice("/"), (None, None));
+    }
+}
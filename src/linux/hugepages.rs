@@ -0,0 +1,142 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::sys::utils::get_all_data;
+use crate::{HugePages, HugePagesPool, NodeHugePages, TransparentHugePages};
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub(crate) fn get_hugepages() -> HugePages {
+    let mut pools = get_pools_sizes(Path::new("/sys/kernel/mm/hugepages"))
+        .into_iter()
+        .map(|(page_size, path)| {
+            let read = |name: &str| read_u64(&path.join(name)).unwrap_or(0);
+            HugePagesPool {
+                page_size,
+                total: read("nr_hugepages"),
+                free: read("free_hugepages"),
+                reserved: read("resv_hugepages"),
+                surplus: read("surplus_hugepages"),
+                overcommit: read("nr_overcommit_hugepages"),
+                nodes: Vec::new(),
+            }
+        })
+        .collect::<Vec<_>>();
+    add_nodes_hugepages(&mut pools);
+    HugePages {
+        pools,
+        transparent: get_transparent_hugepages(),
+    }
+}
+
+/// Returns the sizes (in KB) and paths of the `hugepages-<size>kB` directories, sorted by size.
+fn get_pools_sizes(path: &Path) -> Vec<(u64, PathBuf)> {
+    let mut sizes = match fs::read_dir(path) {
+        Ok(entries) => entries
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let size = parse_pool_size(entry.file_name().to_str()?)?;
+                Some((size, entry.path()))
+            })
+            .collect::<Vec<_>>(),
+        Err(_) => Vec::new(),
+    };
+    sizes.sort_unstable();
+    sizes
+}
+
+fn add_nodes_hugepages(pools: &mut [HugePagesPool]) {
+    let entries = match fs::read_dir("/sys/devices/system/node") {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let node = match entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.strip_prefix("node"))
+            .and_then(|id| id.parse().ok())
+        {
+            Some(node) => node,
+            None => continue,
+        };
+        for (page_size, path) in get_pools_sizes(&entry.path().join("hugepages")) {
+            if let Some(pool) = pools.iter_mut().find(|pool| pool.page_size == page_size) {
+                let read = |name: &str| read_u64(&path.join(name)).unwrap_or(0);
+                pool.nodes.push(NodeHugePages {
+                    node,
+                    total: read("nr_hugepages"),
+                    free: read("free_hugepages"),
+                    surplus: read("surplus_hugepages"),
+                });
+            }
+        }
+    }
+    for pool in pools.iter_mut() {
+        pool.nodes.sort_unstable_by_key(|node| node.node);
+    }
+}
+
+fn get_transparent_hugepages() -> Option<TransparentHugePages> {
+    let path = Path::new("/sys/kernel/mm/transparent_hugepage");
+    let read = |name: &str| get_all_data(path.join(name), 128).ok();
+    let khugepaged = path.join("khugepaged");
+    Some(TransparentHugePages {
+        enabled: parse_selected_value(&read("enabled")?)?.to_owned(),
+        defrag: parse_selected_value(&read("defrag")?)?.to_owned(),
+        khugepaged_defrag: read_u64(&khugepaged.join("defrag")).map(|defrag| defrag != 0),
+        khugepaged_pages_to_scan: read_u64(&khugepaged.join("pages_to_scan")),
+        khugepaged_pages_collapsed: read_u64(&khugepaged.join("pages_collapsed")),
+        khugepaged_full_scans: read_u64(&khugepaged.join("full_scans")),
+    })
+}
+
+fn read_u64(path: &Path) -> Option<u64> {
+    get_all_data(path, 32).ok()?.trim().parse().ok()
+}
+
+/// Parses a `hugepages-<size>kB` directory name.
+fn parse_pool_size(name: &str) -> Option<u64> {
+    name.strip_prefix("hugepages-")?
+        .strip_suffix("kB")?
+        .parse()
+        .ok()
+}
+
+/// Returns the selected value of a setting listing all the possible ones, like
+/// `always [madvise] never`.
+fn parse_selected_value(setting: &str) -> Option<&str> {
+    let start = setting.find('[')? + 1;
+    let end = start + setting[start..].find(']')?;
+    Some(&setting[start..end])
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_pool_size, parse_selected_value};
+
+    #[test]
+    fn check_parse_pool_size() {
+        assert_eq!(parse_pool_size("hugepages-2048kB"), Some(2048));
+        assert_eq!(parse_pool_size("hugepages-1048576kB"), Some(1048576));
+        assert_eq!(parse_pool_size("hugepages-2MB"), None);
+        assert_eq!(parse_pool_size("uevent"), None);
+    }
+
+    #[test]
+    fn check_parse_selected_value() {
+        assert_eq!(
+            parse_selected_value("always [madvise] never\n"),
+            Some("madvise")
+        );
+        assert_eq!(
+            parse_selected_value("always defer [defer+madvise] madvise never\n"),
+            Some("defer+madvise")
+        );
+        assert_eq!(parse_selected_value("always madvise never\n"), None);
+    }
+}
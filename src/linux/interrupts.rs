@@ -0,0 +1,286 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::Interrupt;
+
+/// Parses `/proc/interrupts` or `/proc/softirqs`.
+///
+/// The first line lists the processors (the online ones for the interrupts, all the possible ones
+/// for the softirqs), then there is one line per interrupt:
+///
+/// ```text
+///            CPU0       CPU1
+///   0:         44          0   IO-APIC   2-edge      timer
+///  24:       1208        502   PCI-MSI 327680-edge      xhci_hcd, ahci[0000:00:17.0]
+/// LOC:     287334     289124   Local timer interrupts
+/// ERR:          0
+/// ```
+pub(crate) fn parse_interrupts(data: &str) -> Vec<Interrupt> {
+    let mut lines = data.lines();
+    let cpus = match lines.next() {
+        Some(header) => header
+            .split_whitespace()
+            .filter_map(|cpu| cpu.strip_prefix("CPU")?.parse().ok())
+            .collect::<Vec<usize>>(),
+        None => return Vec::new(),
+    };
+    let nb_cpus = cpus.len();
+    lines
+        .filter_map(|line| {
+            let mut parts = line.splitn(2, ':');
+            let name = parts.next()?.trim();
+            let mut rest = parts.next()?.trim_start();
+            if name.is_empty() {
+                return None;
+            }
+            let mut total_per_cpu = Vec::with_capacity(nb_cpus);
+            while total_per_cpu.len() < nb_cpus {
+                let end = rest
+                    .find(|c: char| c.is_ascii_whitespace())
+                    .unwrap_or(rest.len());
+                match rest[..end].parse() {
+                    Ok(count) => total_per_cpu.push(count),
+                    Err(_) => break,
+                }
+                rest = rest[end..].trim_start();
+            }
+            let description = rest.split_whitespace().collect::<Vec<_>>().join(" ");
+            Some(Interrupt {
+                devices: get_devices(rest),
+                name: name.to_owned(),
+                description,
+                // Interrupts with a single count for all the processors.
+                cpus: if total_per_cpu.len() == nb_cpus {
+                    cpus.clone()
+                } else {
+                    Vec::new()
+                },
+                per_cpu: total_per_cpu.clone(),
+                total_per_cpu,
+            })
+        })
+        .collect()
+}
+
+/// Returns the devices from the description of an IRQ.
+///
+/// They come after the chip name, the hardware IRQ number and the trigger type or flow handler
+/// (like `2-edge`, `9-fasteoi` or `27 Level` depending on the kernel configuration) and are
+/// separated by commas.
+fn get_devices(description: &str) -> Vec<String> {
+    let mut rest = description;
+    loop {
+        let mut parts = rest
+            .trim_start()
+            .splitn(2, |c: char| c.is_ascii_whitespace());
+        let word = match parts.next() {
+            Some(word) if !word.is_empty() => word,
+            _ => return Vec::new(),
+        };
+        rest = parts.next().unwrap_or("");
+        if is_trigger(word) {
+            break;
+        }
+    }
+    rest.split(',')
+        .map(str::trim)
+        .filter(|device| !device.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+fn is_trigger(word: &str) -> bool {
+    let kind = word.split('-').next().unwrap_or(word);
+    if kind == "Edge" || kind == "Level" {
+        return true;
+    }
+    // The hardware IRQ number followed by the flow handler.
+    kind.len() < word.len() && !kind.is_empty() && kind.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Replaces `interrupts` with `new` and computes the counts since the previous refresh.
+pub(crate) fn update_interrupts(interrupts: &mut Vec<Interrupt>, mut new: Vec<Interrupt>) {
+    for (i, interrupt) in new.iter_mut().enumerate() {
+        // The list rarely changes so we first check at the same position.
+        let old = match interrupts.get(i) {
+            Some(old) if old.name == interrupt.name => Some(old),
+            _ => interrupts.iter().find(|old| old.name == interrupt.name),
+        };
+        let old = match old {
+            Some(old) => old,
+            None => continue,
+        };
+        if old.cpus == interrupt.cpus {
+            let old_totals = old.total_per_cpu.iter();
+            for (count, old_total) in interrupt.per_cpu.iter_mut().zip(old_totals) {
+                *count = count.saturating_sub(*old_total);
+            }
+        } else {
+            // A processor went online or offline, so the columns moved.
+            for (count, cpu) in interrupt.per_cpu.iter_mut().zip(interrupt.cpus.iter()) {
+                if let Some(old_total) = old
+                    .cpus
+                    .iter()
+                    .position(|old_cpu| old_cpu == cpu)
+                    .and_then(|pos| old.total_per_cpu.get(pos))
+                {
+                    *count = count.saturating_sub(*old_total);
+                }
+            }
+        }
+    }
+    *interrupts = new;
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_interrupts, update_interrupts};
+    use crate::Interrupt;
+
+    fn interrupt(name: &str, description: &str, devices: &[&str], counts: &[u64]) -> Interrupt {
+        Interrupt {
+            name: name.to_owned(),
+            description: description.to_owned(),
+            devices: devices.iter().map(|d| (*d).to_owned()).collect(),
+            cpus: (0..counts.len()).collect(),
+            total_per_cpu: counts.to_vec(),
+            per_cpu: counts.to_vec(),
+        }
+    }
+
+    fn with_cpus(interrupt: Interrupt, cpus: &[usize]) -> Interrupt {
+        Interrupt {
+            cpus: cpus.to_vec(),
+            ..interrupt
+        }
+    }
+
+    #[test]
+    fn check_parse_interrupts() {
+        assert_eq!(
+            parse_interrupts(
+                "           CPU0       CPU1       \n\
+                 \x20 0:         44          0   IO-APIC   2-edge      timer\n\
+                 \x20 9:          0          3   IO-APIC   9-fasteoi   acpi\n\
+                 \x2024:       1208        502   PCI-MSI 327680-edge      \
+                 xhci_hcd, ahci[0000:00:17.0]\n\
+                 \x2025:          0          0   PCI-MSI 32768-edge\n\
+                 LOC:     287334     289124   Local timer interrupts\n\
+                 ERR:          2\n"
+            ),
+            vec![
+                interrupt("0", "IO-APIC 2-edge timer", &["timer"], &[44, 0]),
+                interrupt("9", "IO-APIC 9-fasteoi acpi", &["acpi"], &[0, 3]),
+                interrupt(
+                    "24",
+                    "PCI-MSI 327680-edge xhci_hcd, ahci[0000:00:17.0]",
+                    &["xhci_hcd", "ahci[0000:00:17.0]"],
+                    &[1208, 502],
+                ),
+                interrupt("25", "PCI-MSI 32768-edge", &[], &[0, 0]),
+                interrupt("LOC", "Local timer interrupts", &[], &[287334, 289124]),
+                with_cpus(interrupt("ERR", "", &[], &[2]), &[]),
+            ]
+        );
+        // Older kernels and ARM.
+        assert_eq!(
+            parse_interrupts(
+                "           CPU0\n\
+                 \x2027:       1234     GICv3  27 Level     arch_timer\n\
+                 \x2028:          5     GICv3  28 Level-fasteoi   arch_mem_timer\n\
+                 IPI0:        10       Rescheduling interrupts\n"
+            ),
+            vec![
+                interrupt("27", "GICv3 27 Level arch_timer", &["arch_timer"], &[1234]),
+                interrupt(
+                    "28",
+                    "GICv3 28 Level-fasteoi arch_mem_timer",
+                    &["arch_mem_timer"],
+                    &[5],
+                ),
+                interrupt("IPI0", "Rescheduling interrupts", &[], &[10]),
+            ]
+        );
+        assert_eq!(parse_interrupts(""), Vec::new());
+    }
+
+    #[test]
+    fn check_parse_softirqs() {
+        assert_eq!(
+            parse_interrupts(
+                "                    CPU0       CPU1       \n\
+                 \x20         HI:          0          1\n\
+                 \x20     NET_RX:       5253        120\n"
+            ),
+            vec![
+                interrupt("HI", "", &[], &[0, 1]),
+                interrupt("NET_RX", "", &[], &[5253, 120]),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_update_interrupts() {
+        let mut interrupts = Vec::new();
+        update_interrupts(
+            &mut interrupts,
+            vec![
+                interrupt("0", "", &[], &[10, 20]),
+                interrupt("1", "", &[], &[5, 5]),
+            ],
+        );
+        assert_eq!(interrupts[0].per_cpu, vec![10, 20]);
+
+        update_interrupts(
+            &mut interrupts,
+            vec![
+                interrupt("1", "", &[], &[7, 5]),
+                interrupt("2", "", &[], &[1, 1]),
+                interrupt("0", "", &[], &[15, 20]),
+            ],
+        );
+        assert_eq!(interrupts[0].per_cpu, vec![2, 0]);
+        assert_eq!(interrupts[1].per_cpu, vec![1, 1]);
+        assert_eq!(interrupts[2].per_cpu, vec![5, 0]);
+        assert_eq!(interrupts[2].total_per_cpu, vec![15, 20]);
+    }
+
+    #[test]
+    fn check_update_interrupts_offline_cpu() {
+        let mut interrupts = Vec::new();
+        update_interrupts(
+            &mut interrupts,
+            parse_interrupts(
+                "           CPU0       CPU1       CPU2\n\
+                 \x20 0:         10         20         30   IO-APIC   2-edge      timer\n\
+                 ERR:          2\n",
+            ),
+        );
+        // CPU1 went offline.
+        update_interrupts(
+            &mut interrupts,
+            parse_interrupts(
+                "           CPU0       CPU2\n\
+                 \x20 0:         15         36   IO-APIC   2-edge      timer\n\
+                 ERR:          3\n",
+            ),
+        );
+        assert_eq!(interrupts[0].cpus, vec![0, 2]);
+        assert_eq!(interrupts[0].per_cpu, vec![5, 6]);
+        assert_eq!(interrupts[1].cpus, Vec::<usize>::new());
+        assert_eq!(interrupts[1].per_cpu, vec![1]);
+        // CPU1 is back online.
+        update_interrupts(
+            &mut interrupts,
+            parse_interrupts(
+                "           CPU0       CPU1       CPU2\n\
+                 \x20 0:         16         21         38   IO-APIC   2-edge      timer\n",
+            ),
+        );
+        assert_eq!(interrupts[0].cpus, vec![0, 1, 2]);
+        assert_eq!(interrupts[0].per_cpu, vec![1, 21, 2]);
+    }
+}
@@ -4,14 +4,20 @@
 // Copyright (c) 2015 Guillaume Gomez
 //
 
+pub(crate) mod cgroup;
 pub mod component;
 pub mod disk;
+pub(crate) mod hugepages;
+pub(crate) mod interrupts;
 pub mod network;
 pub mod process;
 pub mod processor;
+pub(crate) mod swap;
 pub mod system;
+pub(crate) mod topology;
 pub mod users;
 pub(crate) mod utils;
+pub(crate) mod watcher;
 
 pub use self::component::Component;
 pub use self::disk::Disk;
@@ -19,3 +25,4 @@ pub use self::network::{NetworkData, Networks};
 pub use self::process::{Process, ProcessStatus};
 pub use self::processor::Processor;
 pub use self::system::System;
+pub use self::watcher::{ProcessEvent, ProcessWatcher};
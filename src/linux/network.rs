@@ -5,9 +5,10 @@
 //
 
 use std::fs::File;
-use std::io::Read;
+use std::io::{self, Read};
 use std::path::Path;
 
+use crate::sys::utils::{read_u16, read_u32, read_u64, NetlinkSocket};
 use crate::{NetworkExt, NetworksExt, NetworksIter};
 use std::collections::{hash_map, HashMap};
 
@@ -275,9 +276,204 @@ impl NetworkExt for NetworkData {
     }
 }
 
+/// Received and transmitted bytes and packets of a socket or of a network namespace.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct NetCounters {
+    pub(crate) received_bytes: u64,
+    pub(crate) transmitted_bytes: u64,
+    pub(crate) received_packets: u64,
+    pub(crate) transmitted_packets: u64,
+}
+
+impl NetCounters {
+    pub(crate) fn add(&mut self, other: &NetCounters) {
+        self.received_bytes = self.received_bytes.saturating_add(other.received_bytes);
+        self.transmitted_bytes = self
+            .transmitted_bytes
+            .saturating_add(other.transmitted_bytes);
+        self.received_packets = self.received_packets.saturating_add(other.received_packets);
+        self.transmitted_packets = self
+            .transmitted_packets
+            .saturating_add(other.transmitted_packets);
+    }
+
+    pub(crate) fn sub(&self, other: &NetCounters) -> NetCounters {
+        NetCounters {
+            received_bytes: self.received_bytes.saturating_sub(other.received_bytes),
+            transmitted_bytes: self
+                .transmitted_bytes
+                .saturating_sub(other.transmitted_bytes),
+            received_packets: self.received_packets.saturating_sub(other.received_packets),
+            transmitted_packets: self
+                .transmitted_packets
+                .saturating_sub(other.transmitted_packets),
+        }
+    }
+}
+
+/// Sums the counters of all the interfaces listed in a `/proc/[pid]/net/dev` file, except the
+/// loopback one.
+pub(crate) fn parse_net_dev(data: &str) -> NetCounters {
+    let mut counters = NetCounters::default();
+    // The first two lines are headers.
+    for line in data.lines().skip(2) {
+        let mut it = line.splitn(2, ':');
+        let (name, values) = match (it.next(), it.next()) {
+            (Some(name), Some(values)) => (name.trim(), values),
+            _ => continue,
+        };
+        if name == "lo" {
+            continue;
+        }
+        let values = values
+            .split_whitespace()
+            .map(|v| v.parse::<u64>().unwrap_or(0))
+            .collect::<Vec<_>>();
+        if values.len() < 10 {
+            continue;
+        }
+        counters.add(&NetCounters {
+            received_bytes: values[0],
+            received_packets: values[1],
+            transmitted_bytes: values[8],
+            transmitted_packets: values[9],
+        });
+    }
+    counters
+}
+
+const SOCK_DIAG_BY_FAMILY: u16 = 20;
+const INET_DIAG_INFO: u16 = 2;
+// Sizes of `struct nlmsghdr`, `struct inet_diag_req_v2` and `struct inet_diag_msg`.
+const NLMSG_HDR_LEN: usize = 16;
+const INET_DIAG_REQ_LEN: usize = 56;
+const INET_DIAG_MSG_LEN: usize = 72;
+// Offsets in `struct tcp_info`.
+const TCPI_BYTES_ACKED: usize = 120;
+const TCPI_BYTES_RECEIVED: usize = 128;
+const TCPI_SEGS_OUT: usize = 136;
+const TCPI_SEGS_IN: usize = 140;
+
+/// Returns the counters of all the TCP sockets of the current network namespace, indexed by
+/// their inode.
+pub(crate) fn get_tcp_sockets_counters() -> HashMap<u64, NetCounters> {
+    let mut sockets = HashMap::new();
+    for &family in &[libc::AF_INET, libc::AF_INET6] {
+        if let Err(_e) = dump_tcp_sockets(family as u8, &mut sockets) {
+            sysinfo_debug!("failed to list TCP sockets: {:?}", _e);
+        }
+    }
+    sockets
+}
+
+fn dump_tcp_sockets(family: u8, sockets: &mut HashMap<u64, NetCounters>) -> io::Result<()> {
+    let socket = NetlinkSocket::open(libc::NETLINK_SOCK_DIAG, 0)?;
+    socket.send(&inet_diag_request(family))?;
+    let mut buf = vec![0; 32_768];
+    loop {
+        let len = socket.recv(&mut buf, 0)?;
+        if parse_inet_diag_messages(&buf[..len], sockets)? {
+            return Ok(());
+        }
+    }
+}
+
+fn inet_diag_request(family: u8) -> Vec<u8> {
+    let len = NLMSG_HDR_LEN + INET_DIAG_REQ_LEN;
+    let mut msg = Vec::with_capacity(len);
+    // `struct nlmsghdr`
+    msg.extend_from_slice(&(len as u32).to_ne_bytes());
+    msg.extend_from_slice(&SOCK_DIAG_BY_FAMILY.to_ne_bytes());
+    msg.extend_from_slice(&((libc::NLM_F_REQUEST | libc::NLM_F_DUMP) as u16).to_ne_bytes());
+    msg.extend_from_slice(&0u32.to_ne_bytes());
+    msg.extend_from_slice(&0u32.to_ne_bytes());
+    // `struct inet_diag_req_v2`: we want the `tcp_info` of the sockets in all the states.
+    msg.push(family);
+    msg.push(libc::IPPROTO_TCP as u8);
+    msg.push(1 << (INET_DIAG_INFO - 1));
+    msg.push(0);
+    msg.extend_from_slice(&u32::MAX.to_ne_bytes());
+    // `struct inet_diag_sockid`, unused when dumping.
+    msg.resize(len, 0);
+    msg
+}
+
+/// Parses the netlink messages contained in `buf` and adds the counters of the sockets to
+/// `sockets`.
+///
+/// Returns `true` once the end of the dump has been reached.
+fn parse_inet_diag_messages(
+    mut buf: &[u8],
+    sockets: &mut HashMap<u64, NetCounters>,
+) -> io::Result<bool> {
+    while let Some(len) = read_u32(buf, 0) {
+        let len = len as usize;
+        if len < NLMSG_HDR_LEN || len > buf.len() {
+            break;
+        }
+        let msg = &buf[..len];
+        match read_u16(msg, 4).map(|t| t as i32) {
+            Some(libc::NLMSG_DONE) => return Ok(true),
+            Some(libc::NLMSG_ERROR) => {
+                let err = read_u32(msg, NLMSG_HDR_LEN).unwrap_or(0) as i32;
+                return if err == 0 {
+                    Ok(true)
+                } else {
+                    Err(io::Error::from_raw_os_error(-err))
+                };
+            }
+            Some(t) if t == SOCK_DIAG_BY_FAMILY as i32 => {
+                let data = &msg[NLMSG_HDR_LEN..];
+                if let Some(inode) = read_u32(data, 68) {
+                    if let Some(counters) = parse_tcp_info_attribute(data) {
+                        sockets.insert(inode as u64, counters);
+                    }
+                }
+            }
+            _ => {}
+        }
+        // Messages are aligned on 4 bytes.
+        let next = (len + 3) & !3;
+        if next >= buf.len() {
+            break;
+        }
+        buf = &buf[next..];
+    }
+    Ok(false)
+}
+
+fn parse_tcp_info_attribute(data: &[u8]) -> Option<NetCounters> {
+    let mut attrs = data.get(INET_DIAG_MSG_LEN..)?;
+    while let (Some(len), Some(kind)) = (read_u16(attrs, 0), read_u16(attrs, 2)) {
+        let len = len as usize;
+        if len < 4 || len > attrs.len() {
+            break;
+        }
+        if kind == INET_DIAG_INFO {
+            // Older kernels have a smaller `tcp_info`, missing fields are considered to be 0.
+            let info = &attrs[4..len];
+            return Some(NetCounters {
+                received_bytes: read_u64(info, TCPI_BYTES_RECEIVED).unwrap_or(0),
+                transmitted_bytes: read_u64(info, TCPI_BYTES_ACKED).unwrap_or(0),
+                received_packets: read_u32(info, TCPI_SEGS_IN).unwrap_or(0) as u64,
+                transmitted_packets: read_u32(info, TCPI_SEGS_OUT).unwrap_or(0) as u64,
+            });
+        }
+        let next = (len + 3) & !3;
+        if next >= attrs.len() {
+            break;
+        }
+        attrs = &attrs[next..];
+    }
+    None
+}
+
 #[cfg(test)]
 mod test {
-    use super::refresh_networks_list_from_sysfs;
+    use super::{
+        parse_inet_diag_messages, parse_net_dev, refresh_networks_list_from_sysfs, NetCounters,
+        INET_DIAG_INFO, INET_DIAG_MSG_LEN, NLMSG_HDR_LEN, SOCK_DIAG_BY_FAMILY,
+    };
     use std::collections::HashMap;
     use std::fs;
 
@@ -321,4 +517,82 @@ mod test {
         refresh_networks_list_from_sysfs(&mut interfaces, sys_net_dir.path());
         assert_eq!(interfaces.keys().collect::<Vec<_>>(), ["itf2"]);
     }
+
+    #[test]
+    fn check_parse_net_dev() {
+        let data = "\
+Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo:  1000      10    0    0    0     0          0         0     1000      10    0    0    0     0       0          0
+  eth0:  2000      20    0    0    0     0          0         0     3000      30    0    0    0     0       0          0
+  eth1:5000        50    0    0    0     0          0         0     7000      70    0    0    0     0       0          0
+";
+        assert_eq!(
+            parse_net_dev(data),
+            NetCounters {
+                received_bytes: 7000,
+                transmitted_bytes: 10_000,
+                received_packets: 70,
+                transmitted_packets: 100,
+            }
+        );
+    }
+
+    fn inet_diag_message(inode: u32, tcp_info: &[u8]) -> Vec<u8> {
+        let len = NLMSG_HDR_LEN + INET_DIAG_MSG_LEN + 4 + tcp_info.len();
+        let mut msg = Vec::new();
+        msg.extend_from_slice(&(len as u32).to_ne_bytes());
+        msg.extend_from_slice(&SOCK_DIAG_BY_FAMILY.to_ne_bytes());
+        msg.resize(NLMSG_HDR_LEN + 68, 0);
+        msg.extend_from_slice(&inode.to_ne_bytes());
+        msg.extend_from_slice(&((4 + tcp_info.len()) as u16).to_ne_bytes());
+        msg.extend_from_slice(&INET_DIAG_INFO.to_ne_bytes());
+        msg.extend_from_slice(tcp_info);
+        assert_eq!(msg.len(), len);
+        msg
+    }
+
+    #[test]
+    fn check_parse_inet_diag_messages() {
+        let mut tcp_info = vec![0; 160];
+        tcp_info[120..128].copy_from_slice(&1000u64.to_ne_bytes());
+        tcp_info[128..136].copy_from_slice(&2000u64.to_ne_bytes());
+        tcp_info[136..140].copy_from_slice(&10u32.to_ne_bytes());
+        tcp_info[140..144].copy_from_slice(&20u32.to_ne_bytes());
+        let mut buf = inet_diag_message(42, &tcp_info);
+        // Older kernels don't have the counters.
+        buf.extend(inet_diag_message(43, &[0; 104]));
+
+        let mut sockets = HashMap::new();
+        assert!(!parse_inet_diag_messages(&buf, &mut sockets).unwrap());
+        assert_eq!(sockets.len(), 2);
+        assert_eq!(
+            sockets[&42],
+            NetCounters {
+                received_bytes: 2000,
+                transmitted_bytes: 1000,
+                received_packets: 20,
+                transmitted_packets: 10,
+            }
+        );
+        assert_eq!(sockets[&43], NetCounters::default());
+
+        let mut done = Vec::new();
+        done.extend_from_slice(&20u32.to_ne_bytes());
+        done.extend_from_slice(&(libc::NLMSG_DONE as u16).to_ne_bytes());
+        done.resize(20, 0);
+        assert!(parse_inet_diag_messages(&done, &mut sockets).unwrap());
+
+        let mut error = Vec::new();
+        error.extend_from_slice(&20u32.to_ne_bytes());
+        error.extend_from_slice(&(libc::NLMSG_ERROR as u16).to_ne_bytes());
+        error.resize(16, 0);
+        error.extend_from_slice(&(-libc::EPERM).to_ne_bytes());
+        assert_eq!(
+            parse_inet_diag_messages(&error, &mut sockets)
+                .unwrap_err()
+                .raw_os_error(),
+            Some(libc::EPERM)
+        );
+    }
 }
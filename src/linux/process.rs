@@ -6,21 +6,31 @@
 
 use std::cell::UnsafeCell;
 use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
 use std::fmt;
 use std::fs::{self, File};
-use std::io::Read;
+use std::io::{self, Read};
+use std::os::unix::ffi::OsStrExt;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use libc::{gid_t, kill, sysconf, uid_t, _SC_CLK_TCK};
+use libc::{c_int, gid_t, kill, sysconf, uid_t, _SC_CLK_TCK};
 
+use crate::sys::cgroup::{
+    get_container, get_systemd_unit_and_slice, parse_cgroup_paths, parse_systemd_cgroup_path,
+};
+use crate::sys::network::{parse_net_dev, NetCounters};
 use crate::sys::system::REMAINING_FILES;
 use crate::sys::utils::{get_all_data, get_all_data_from_file};
 use crate::utils::{into_iter, realpath};
-use crate::{DiskUsage, Pid, ProcessExt, Signal};
+use crate::{
+    Container, ContextSwitches, CpuTime, DiskUsage, IoStats, Namespaces, NetworkUsage, PageFaults,
+    Pid, ProcessExt, ProcessRefreshKind, SchedulingInfo, SchedulingPolicy, Signal, Uid,
+};
 
 /// Enum describing the different status of a process.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ProcessStatus {
     /// Waiting in uninterruptible disk sleep.
     Idle,
@@ -106,10 +116,12 @@ impl fmt::Display for ProcessStatus {
 pub struct Process {
     pub(crate) name: String,
     pub(crate) cmd: Vec<String>,
+    pub(crate) cmd_os: Vec<OsString>,
     pub(crate) exe: PathBuf,
     pub(crate) pid: Pid,
     parent: Option<Pid>,
     pub(crate) environ: Vec<String>,
+    pub(crate) environ_os: Vec<OsString>,
     pub(crate) cwd: PathBuf,
     pub(crate) root: PathBuf,
     pub(crate) memory: u64,
@@ -119,6 +131,8 @@ pub struct Process {
     old_utime: u64,
     old_stime: u64,
     start_time: u64,
+    start_time_precise: SystemTime,
+    pub(crate) start_time_ticks: u64,
     updated: bool,
     cpu_usage: f32,
     /// User id of the process owner.
@@ -133,6 +147,30 @@ pub struct Process {
     old_written_bytes: u64,
     read_bytes: u64,
     written_bytes: u64,
+    old_io: IoCounters,
+    io: IoCounters,
+    scheduling: SchedulingInfo,
+    old_voluntary_ctxt_switches: u64,
+    old_involuntary_ctxt_switches: u64,
+    voluntary_ctxt_switches: u64,
+    involuntary_ctxt_switches: u64,
+    old_minor_faults: u64,
+    old_major_faults: u64,
+    minor_faults: u64,
+    major_faults: u64,
+    old_net: NetCounters,
+    net: NetCounters,
+    net_sockets: HashMap<u64, NetCounters>,
+    namespaces: Namespaces,
+    namespace_pid: Option<Pid>,
+    container: Option<Container>,
+    systemd_unit: Option<String>,
+    systemd_slice: Option<String>,
+    cgroups: Vec<String>,
+    session_id: Option<Pid>,
+    process_group_id: Option<Pid>,
+    tty_nr: u32,
+    tty: Option<PathBuf>,
 }
 
 impl ProcessExt for Process {
@@ -142,7 +180,9 @@ impl ProcessExt for Process {
             pid,
             parent,
             cmd: Vec::with_capacity(2),
+            cmd_os: Vec::with_capacity(2),
             environ: Vec::with_capacity(10),
+            environ_os: Vec::with_capacity(10),
             exe: PathBuf::new(),
             cwd: PathBuf::new(),
             root: PathBuf::new(),
@@ -155,6 +195,8 @@ impl ProcessExt for Process {
             old_stime: 0,
             updated: true,
             start_time,
+            start_time_precise: UNIX_EPOCH + Duration::from_secs(start_time),
+            start_time_ticks: 0,
             uid: 0,
             gid: 0,
             status: ProcessStatus::Unknown(0),
@@ -168,10 +210,38 @@ impl ProcessExt for Process {
             old_written_bytes: 0,
             read_bytes: 0,
             written_bytes: 0,
+            old_io: IoCounters::default(),
+            io: IoCounters::default(),
+            scheduling: SchedulingInfo::default(),
+            old_voluntary_ctxt_switches: 0,
+            old_involuntary_ctxt_switches: 0,
+            voluntary_ctxt_switches: 0,
+            involuntary_ctxt_switches: 0,
+            old_minor_faults: 0,
+            old_major_faults: 0,
+            minor_faults: 0,
+            major_faults: 0,
+            old_net: NetCounters::default(),
+            net: NetCounters::default(),
+            net_sockets: HashMap::new(),
+            namespaces: Namespaces::default(),
+            namespace_pid: None,
+            container: None,
+            systemd_unit: None,
+            systemd_slice: None,
+            cgroups: Vec::new(),
+            session_id: None,
+            process_group_id: None,
+            tty_nr: 0,
+            tty: None,
         }
     }
 
     fn kill(&self, signal: Signal) -> bool {
+        self.try_kill(signal).is_ok()
+    }
+
+    fn try_kill(&self, signal: Signal) -> io::Result<()> {
         let c_signal = match signal {
             Signal::Hangup => libc::SIGHUP,
             Signal::Interrupt => libc::SIGINT,
@@ -206,7 +276,11 @@ impl ProcessExt for Process {
             Signal::Power => libc::SIGPWR,
             Signal::Sys => libc::SIGSYS,
         };
-        unsafe { kill(self.pid, c_signal) == 0 }
+        if unsafe { kill(self.pid, c_signal) } == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
     }
 
     fn name(&self) -> &str {
@@ -229,6 +303,14 @@ impl ProcessExt for Process {
         &self.environ
     }
 
+    fn cmd_os(&self) -> &[OsString] {
+        &self.cmd_os
+    }
+
+    fn environ_os(&self) -> &[OsString] {
+        &self.environ_os
+    }
+
     fn cwd(&self) -> &Path {
         self.cwd.as_path()
     }
@@ -255,14 +337,72 @@ impl ProcessExt for Process {
         self.status
     }
 
+    fn user_id(&self) -> Option<Uid> {
+        Some(Uid(self.uid))
+    }
+
+    fn namespaces(&self) -> Namespaces {
+        self.namespaces
+    }
+
+    fn namespace_pid(&self) -> Option<Pid> {
+        self.namespace_pid
+    }
+
+    fn container(&self) -> Option<&Container> {
+        self.container.as_ref()
+    }
+
+    fn systemd_unit(&self) -> Option<&str> {
+        self.systemd_unit.as_deref()
+    }
+
+    fn systemd_slice(&self) -> Option<&str> {
+        self.systemd_slice.as_deref()
+    }
+
+    fn cgroups(&self) -> &[String] {
+        &self.cgroups
+    }
+
+    fn session_id(&self) -> Option<Pid> {
+        self.session_id
+    }
+
+    fn process_group_id(&self) -> Option<Pid> {
+        self.process_group_id
+    }
+
+    fn tty(&self) -> Option<&Path> {
+        self.tty.as_deref()
+    }
+
     fn start_time(&self) -> u64 {
         self.start_time
     }
 
+    fn start_time_precise(&self) -> SystemTime {
+        self.start_time_precise
+    }
+
+    fn run_time(&self) -> Duration {
+        SystemTime::now()
+            .duration_since(self.start_time_precise)
+            .unwrap_or_default()
+    }
+
     fn cpu_usage(&self) -> f32 {
         self.cpu_usage
     }
 
+    fn cpu_time(&self) -> CpuTime {
+        let clock_cycle = clock_cycle();
+        CpuTime {
+            user: ticks_to_duration(self.utime, clock_cycle),
+            system: ticks_to_duration(self.stime, clock_cycle),
+        }
+    }
+
     fn disk_usage(&self) -> DiskUsage {
         DiskUsage {
             written_bytes: self.written_bytes - self.old_written_bytes,
@@ -271,6 +411,197 @@ impl ProcessExt for Process {
             total_read_bytes: self.read_bytes,
         }
     }
+
+    fn io_stats(&self) -> IoStats {
+        IoStats {
+            total_read_chars: self.io.rchar,
+            read_chars: self.io.rchar.saturating_sub(self.old_io.rchar),
+            total_written_chars: self.io.wchar,
+            written_chars: self.io.wchar.saturating_sub(self.old_io.wchar),
+            total_read_syscalls: self.io.syscr,
+            read_syscalls: self.io.syscr.saturating_sub(self.old_io.syscr),
+            total_write_syscalls: self.io.syscw,
+            write_syscalls: self.io.syscw.saturating_sub(self.old_io.syscw),
+            total_cancelled_written_bytes: self.io.cancelled_write_bytes,
+            cancelled_written_bytes: self
+                .io
+                .cancelled_write_bytes
+                .saturating_sub(self.old_io.cancelled_write_bytes),
+        }
+    }
+
+    fn network_usage(&self) -> NetworkUsage {
+        let new = self.net.sub(&self.old_net);
+        NetworkUsage {
+            total_received_bytes: self.net.received_bytes,
+            received_bytes: new.received_bytes,
+            total_transmitted_bytes: self.net.transmitted_bytes,
+            transmitted_bytes: new.transmitted_bytes,
+            total_received_packets: self.net.received_packets,
+            received_packets: new.received_packets,
+            total_transmitted_packets: self.net.transmitted_packets,
+            transmitted_packets: new.transmitted_packets,
+        }
+    }
+
+    fn scheduling(&self) -> SchedulingInfo {
+        self.scheduling
+    }
+
+    fn cpu_affinity(&self) -> Vec<usize> {
+        let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+        let size = std::mem::size_of::<libc::cpu_set_t>();
+        if unsafe { libc::sched_getaffinity(self.pid, size, &mut set) } != 0 {
+            return Vec::new();
+        }
+        (0..size * 8)
+            .filter(|&cpu| unsafe { libc::CPU_ISSET(cpu, &set) })
+            .collect()
+    }
+
+    fn context_switches(&self) -> ContextSwitches {
+        ContextSwitches {
+            total_voluntary: self.voluntary_ctxt_switches,
+            voluntary: self
+                .voluntary_ctxt_switches
+                .saturating_sub(self.old_voluntary_ctxt_switches),
+            total_involuntary: self.involuntary_ctxt_switches,
+            involuntary: self
+                .involuntary_ctxt_switches
+                .saturating_sub(self.old_involuntary_ctxt_switches),
+        }
+    }
+
+    fn page_faults(&self) -> PageFaults {
+        PageFaults {
+            total_minor: self.minor_faults,
+            minor: self.minor_faults.saturating_sub(self.old_minor_faults),
+            total_major: self.major_faults,
+            major: self.major_faults.saturating_sub(self.old_major_faults),
+        }
+    }
+
+    fn set_priority(&self, nice: i32) -> io::Result<()> {
+        if unsafe { libc::setpriority(libc::PRIO_PROCESS as _, self.pid as _, nice) } == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+
+    fn set_cpu_affinity(&self, processors: &[usize]) -> io::Result<()> {
+        let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+        let size = std::mem::size_of::<libc::cpu_set_t>();
+        for &cpu in processors {
+            if cpu >= size * 8 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "processor index out of range",
+                ));
+            }
+            unsafe { libc::CPU_SET(cpu, &mut set) };
+        }
+        if unsafe { libc::sched_setaffinity(self.pid, size, &set) } == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+
+    fn suspend(&self) -> io::Result<()> {
+        self.try_kill(Signal::Stop)
+    }
+
+    fn resume(&self) -> io::Result<()> {
+        self.try_kill(Signal::Continue)
+    }
+
+    fn wait_for_exit(&self, timeout: Option<Duration>) -> io::Result<bool> {
+        let deadline = timeout.and_then(|timeout| Instant::now().checked_add(timeout));
+        let pidfd = unsafe { libc::syscall(libc::SYS_pidfd_open, self.pid, 0) };
+        if pidfd < 0 {
+            let err = io::Error::last_os_error();
+            return match err.raw_os_error() {
+                Some(libc::ESRCH) => Ok(true),
+                // pidfd isn't supported by this kernel, so we check regularly instead.
+                Some(libc::ENOSYS) => Ok(poll_for_exit(self, deadline)),
+                _ => Err(err),
+            };
+        }
+        let pidfd = PidFd(pidfd as c_int);
+        // The pid might have been reused between the last refresh and the pidfd opening, in which
+        // case the process we're looking for already exited.
+        if has_exited(self) {
+            return Ok(true);
+        }
+        let mut poll_fd = libc::pollfd {
+            fd: pidfd.0,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        loop {
+            let timeout_ms = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    // Rounded up so we don't wake up just before the deadline.
+                    let ms = remaining.as_millis()
+                        + u128::from(remaining.subsec_nanos() % 1_000_000 != 0);
+                    ms.min(c_int::MAX as u128) as c_int
+                }
+                None => -1,
+            };
+            match unsafe { libc::poll(&mut poll_fd, 1, timeout_ms) } {
+                x if x > 0 => return Ok(true),
+                0 => return Ok(false),
+                _ => {
+                    let err = io::Error::last_os_error();
+                    if err.kind() != io::ErrorKind::Interrupted {
+                        return Err(err);
+                    }
+                }
+            }
+        }
+    }
+}
+
+struct PidFd(c_int);
+
+impl Drop for PidFd {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.0);
+        }
+    }
+}
+
+/// Returns `true` if the process is gone, is a zombie or if its pid was reused by another process.
+fn has_exited(p: &Process) -> bool {
+    let data = match get_all_data(format!("/proc/{}/stat", p.pid), 1024) {
+        Ok(data) => data,
+        Err(_) => return true,
+    };
+    match parse_stat_file(&data) {
+        Ok(parts) => {
+            parts[2].starts_with('Z')
+                || parts[2].starts_with('X')
+                || parse_part::<u64>(&parts, 21) != p.start_time_ticks
+        }
+        Err(_) => true,
+    }
+}
+
+fn poll_for_exit(p: &Process, deadline: Option<Instant>) -> bool {
+    loop {
+        if has_exited(p) {
+            return true;
+        }
+        if let Some(deadline) = deadline {
+            if Instant::now() >= deadline {
+                return false;
+            }
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
 }
 
 impl Drop for Process {
@@ -283,7 +614,22 @@ impl Drop for Process {
     }
 }
 
+fn clock_cycle() -> u64 {
+    match unsafe { sysconf(_SC_CLK_TCK) } {
+        x if x > 0 => x as u64,
+        _ => 100,
+    }
+}
+
+fn ticks_to_duration(ticks: u64, clock_cycle: u64) -> Duration {
+    Duration::from_secs(ticks / clock_cycle)
+        + Duration::from_nanos((ticks % clock_cycle) * 1_000_000_000 / clock_cycle)
+}
+
 pub fn compute_cpu_usage(p: &mut Process, nb_processors: u64, total_time: f32) {
+    // Reset even when the usage can't be computed yet, otherwise processes which never used any
+    // CPU time would never be removed once dead.
+    p.updated = false;
     // First time updating the values without reference, wait for a second cycle to update cpu_usage
     if p.old_utime == 0 && p.old_stime == 0 {
         return;
@@ -293,7 +639,6 @@ pub fn compute_cpu_usage(p: &mut Process, nb_processors: u64, total_time: f32) {
         * nb_processors
         * 100) as f32
         / total_time;
-    p.updated = false;
 }
 
 pub fn set_time(p: &mut Process, utime: u64, stime: u64) {
@@ -308,6 +653,16 @@ pub fn has_been_updated(p: &Process) -> bool {
     p.updated
 }
 
+/// Counters of `/proc/[pid]/io` which aren't about storage.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct IoCounters {
+    rchar: u64,
+    wchar: u64,
+    syscr: u64,
+    syscw: u64,
+    cancelled_write_bytes: u64,
+}
+
 pub(crate) fn update_process_disk_activity(p: &mut Process, path: &Path) {
     let mut path = PathBuf::from(path);
     path.push("io");
@@ -315,34 +670,88 @@ pub(crate) fn update_process_disk_activity(p: &mut Process, path: &Path) {
         Ok(d) => d,
         Err(_) => return,
     };
-    let mut done = 0;
+    p.old_read_bytes = p.read_bytes;
+    p.old_written_bytes = p.written_bytes;
+    p.old_io = p.io;
     for line in data.split('\n') {
         let mut parts = line.split(": ");
-        match parts.next() {
-            Some("read_bytes") => {
-                p.old_read_bytes = p.read_bytes;
-                p.read_bytes = parts
-                    .next()
-                    .and_then(|x| x.parse::<u64>().ok())
-                    .unwrap_or(p.old_read_bytes);
-            }
-            Some("write_bytes") => {
-                p.old_written_bytes = p.written_bytes;
-                p.written_bytes = parts
-                    .next()
-                    .and_then(|x| x.parse::<u64>().ok())
-                    .unwrap_or(p.old_written_bytes);
-            }
+        let field = match parts.next() {
+            Some("rchar") => &mut p.io.rchar,
+            Some("wchar") => &mut p.io.wchar,
+            Some("syscr") => &mut p.io.syscr,
+            Some("syscw") => &mut p.io.syscw,
+            Some("read_bytes") => &mut p.read_bytes,
+            Some("write_bytes") => &mut p.written_bytes,
+            Some("cancelled_write_bytes") => &mut p.io.cancelled_write_bytes,
             _ => continue,
-        }
-        done += 1;
-        if done > 1 {
-            // No need to continue the reading.
-            break;
+        };
+        if let Some(value) = parts.next().and_then(|x| x.parse::<u64>().ok()) {
+            *field = value;
         }
     }
 }
 
+/// Updates the network usage of `p`.
+///
+/// `net_ns` is the network namespace of the current process and `sockets` contains the counters
+/// of the TCP sockets of this namespace. If `p` is in another namespace, the counters of all its
+/// interfaces are used instead. If one of the namespaces is unknown (because of missing
+/// permissions for example), `p` is considered to be in the same namespace.
+pub(crate) fn update_process_network_usage(
+    p: &mut Process,
+    path: &Path,
+    net_ns: Option<&Path>,
+    sockets: &HashMap<u64, NetCounters>,
+) {
+    let other_namespace = match (fs::read_link(path.join("ns/net")), net_ns) {
+        (Ok(ns), Some(net_ns)) => ns != net_ns,
+        _ => false,
+    };
+    let net = if other_namespace {
+        p.net_sockets.clear();
+        match get_all_data(path.join("net/dev"), 4096) {
+            Ok(data) => parse_net_dev(&data),
+            Err(_) => return,
+        }
+    } else {
+        // Sockets come and go so we add what each of them did since the last refresh.
+        let mut net = p.net;
+        let mut net_sockets = HashMap::with_capacity(p.net_sockets.len());
+        if let Ok(entries) = fs::read_dir(path.join("fd")) {
+            for entry in entries.flatten() {
+                let inode = match fs::read_link(entry.path())
+                    .ok()
+                    .and_then(|link| get_socket_inode(&link))
+                {
+                    Some(inode) => inode,
+                    None => continue,
+                };
+                if net_sockets.contains_key(&inode) {
+                    // Duplicated file descriptor.
+                    continue;
+                }
+                if let Some(counters) = sockets.get(&inode) {
+                    let old = p.net_sockets.get(&inode).copied().unwrap_or_default();
+                    net.add(&counters.sub(&old));
+                    net_sockets.insert(inode, *counters);
+                }
+            }
+        }
+        p.net_sockets = net_sockets;
+        net
+    };
+    p.old_net = p.net;
+    p.net = net;
+}
+
+fn get_socket_inode(link: &Path) -> Option<u64> {
+    link.to_str()?
+        .strip_prefix("socket:[")?
+        .strip_suffix(']')?
+        .parse()
+        .ok()
+}
+
 struct Wrap<'a, T>(UnsafeCell<&'a mut T>);
 
 impl<'a, T> Wrap<'a, T> {
@@ -359,8 +768,9 @@ pub(crate) fn _get_process_data(
     proc_list: &mut Process,
     page_size_kb: u64,
     pid: Pid,
-    uptime: u64,
-    now: u64,
+    uptime: Duration,
+    now: Duration,
+    refresh_kind: ProcessRefreshKind,
 ) -> Result<(Option<Process>, Pid), ()> {
     let nb = match path.file_name().and_then(|x| x.to_str()).map(Pid::from_str) {
         Some(Ok(nb)) if nb != pid => nb,
@@ -376,40 +786,83 @@ pub(crate) fn _get_process_data(
     };
     let parent_memory = proc_list.memory;
     let parent_virtual_memory = proc_list.virtual_memory;
-    if let Some(ref mut entry) = proc_list.tasks.get_mut(&nb) {
-        let data = if let Some(ref mut f) = entry.stat_file {
-            get_all_data_from_file(f, 1024).map_err(|_| ())?
-        } else {
-            let mut tmp = PathBuf::from(path);
-            tmp.push("stat");
-            let mut file = File::open(tmp).map_err(|_| ())?;
-            let data = get_all_data_from_file(&mut file, 1024).map_err(|_| ())?;
-            entry.stat_file = check_nb_open_files(file);
-            data
-        };
-        let parts = parse_stat_file(&data)?;
-        get_status(entry, parts[2]);
-        update_time_and_memory(
-            path,
-            entry,
-            &parts,
-            page_size_kb,
-            parent_memory,
-            parent_virtual_memory,
-            nb,
-            uptime,
-            now,
-        );
-        update_process_disk_activity(entry, path);
-        return Ok((None, nb));
-    }
-
+    let is_task = proc_list.pid != 0;
     let mut tmp = PathBuf::from(path);
-
     tmp.push("stat");
-    let mut file = std::fs::File::open(&tmp).map_err(|_| ())?;
-    let data = get_all_data_from_file(&mut file, 1024).map_err(|_| ())?;
-    let stat_file = check_nb_open_files(file);
+
+    let (data, stat_file) = if let Some(ref mut entry) = proc_list.tasks.get_mut(&nb) {
+        let data = match entry.stat_file {
+            Some(ref mut f) => get_all_data_from_file(f, 1024).ok(),
+            None => None,
+        };
+        let data = match data {
+            Some(data) => data,
+            None => {
+                // Either we didn't keep the file open or the process it belonged to is gone (in
+                // which case the pid might have been reused by another process).
+                let mut file = File::open(&tmp).map_err(|_| ())?;
+                let data = get_all_data_from_file(&mut file, 1024).map_err(|_| ())?;
+                if entry.stat_file.is_some() {
+                    entry.stat_file = Some(file);
+                } else {
+                    entry.stat_file = check_nb_open_files(file);
+                }
+                data
+            }
+        };
+        let parts = parse_stat_file(&data)?;
+        if u64::from_str(parts[21]).unwrap_or(0) != entry.start_time_ticks {
+            // The start time changed, meaning that the pid was reused by another process. We
+            // handle it as a new process which will replace the old entry.
+            let stat_file = entry.stat_file.take();
+            (data, stat_file)
+        } else {
+            get_status(entry, parts[2]);
+            if !is_task && has_executed(entry, parts[1], path, refresh_kind) {
+                // The process executed another program (or was renamed), so the information we
+                // only retrieve when the process is listed is very likely outdated.
+                entry.name = parts[1].to_owned();
+                clear_process_info(entry);
+                update_process_info(entry, path, refresh_kind);
+            } else if !is_task {
+                // Unlike the rest, the namespaces and the cgroups can change without `exec` (with
+                // `setns`, `unshare` or when the process is moved to another cgroup).
+                if refresh_kind.namespaces() {
+                    entry.namespaces = get_namespaces(&path.join("ns"));
+                }
+                if refresh_kind.cgroup() {
+                    update_cgroup_info(entry, path);
+                }
+            }
+            if refresh_kind.context_switches() {
+                tmp.pop();
+                tmp.push("status");
+                if let Ok(data) = get_all_data(&tmp, 16_385) {
+                    update_context_switches(entry, &data);
+                }
+            }
+            update_time_and_memory(
+                path,
+                entry,
+                &parts,
+                page_size_kb,
+                parent_memory,
+                parent_virtual_memory,
+                nb,
+                uptime,
+                now,
+                refresh_kind,
+            );
+            if refresh_kind.disk_usage() {
+                update_process_disk_activity(entry, path);
+            }
+            return Ok((None, nb));
+        }
+    } else {
+        let mut file = File::open(&tmp).map_err(|_| ())?;
+        let data = get_all_data_from_file(&mut file, 1024).map_err(|_| ())?;
+        (data, check_nb_open_files(file))
+    };
     let parts = parse_stat_file(&data)?;
     let name = parts[1];
 
@@ -422,60 +875,50 @@ pub(crate) fn _get_process_data(
         }
     };
 
-    let clock_cycle = unsafe { sysconf(_SC_CLK_TCK) } as u64;
-    let since_boot = u64::from_str(parts[21]).unwrap_or(0) / clock_cycle;
-    let start_time = now.saturating_sub(uptime.saturating_sub(since_boot));
-    let mut p = Process::new(nb, parent_pid, start_time);
+    let start_time_ticks = u64::from_str(parts[21]).unwrap_or(0);
+    let since_boot = ticks_to_duration(start_time_ticks, clock_cycle());
+    let start_time = now
+        .checked_sub(uptime.checked_sub(since_boot).unwrap_or_default())
+        .unwrap_or_default();
+    let mut p = Process::new(nb, parent_pid, start_time.as_secs());
 
+    p.start_time_precise = UNIX_EPOCH + start_time;
+    p.start_time_ticks = start_time_ticks;
     p.stat_file = stat_file;
     get_status(&mut p, parts[2]);
 
     tmp.pop();
     tmp.push("status");
     if let Ok(data) = get_all_data(&tmp, 16_385) {
-        if let Some((uid, gid)) = _get_uid_and_gid(data) {
+        if let Some((uid, gid)) = _get_uid_and_gid(&data) {
             p.uid = uid;
             p.gid = gid;
         }
+        p.namespace_pid = _get_namespace_pid(&data);
+        if refresh_kind.context_switches() {
+            update_context_switches(&mut p, &data);
+        }
     }
 
     if proc_list.pid != 0 {
         // If we're getting information for a child, no need to get those info since we
         // already have them...
         p.cmd = proc_list.cmd.clone();
+        p.cmd_os = proc_list.cmd_os.clone();
         p.name = proc_list.name.clone();
         p.environ = proc_list.environ.clone();
+        p.environ_os = proc_list.environ_os.clone();
         p.exe = proc_list.exe.clone();
         p.cwd = proc_list.cwd.clone();
         p.root = proc_list.root.clone();
+        p.namespaces = proc_list.namespaces;
+        p.container = proc_list.container.clone();
+        p.systemd_unit = proc_list.systemd_unit.clone();
+        p.systemd_slice = proc_list.systemd_slice.clone();
+        p.cgroups = proc_list.cgroups.clone();
     } else {
         p.name = name.into();
-        tmp.pop();
-        tmp.push("cmdline");
-        p.cmd = copy_from_file(&tmp);
-        tmp.pop();
-        tmp.push("exe");
-        match tmp.read_link() {
-            Ok(exe_path) => {
-                p.exe = exe_path;
-            }
-            Err(_) => {
-                p.exe = if let Some(cmd) = p.cmd.get(0) {
-                    PathBuf::from(cmd)
-                } else {
-                    PathBuf::new()
-                };
-            }
-        }
-        tmp.pop();
-        tmp.push("environ");
-        p.environ = copy_from_file(&tmp);
-        tmp.pop();
-        tmp.push("cwd");
-        p.cwd = realpath(&tmp);
-        tmp.pop();
-        tmp.push("root");
-        p.root = realpath(&tmp);
+        update_process_info(&mut p, path, refresh_kind);
     }
 
     update_time_and_memory(
@@ -488,11 +931,219 @@ pub(crate) fn _get_process_data(
         nb,
         uptime,
         now,
+        refresh_kind,
     );
-    update_process_disk_activity(&mut p, path);
+    if refresh_kind.disk_usage() {
+        update_process_disk_activity(&mut p, path);
+    }
     Ok((Some(p), nb))
 }
 
+/// Returns `true` if the name, the executable or the command line (if `refresh_kind` asks for
+/// it) of the process changed. The name alone isn't enough since the new program can have the
+/// same one (a shell running `exec sh`, for example).
+fn has_executed(p: &Process, name: &str, path: &Path, refresh_kind: ProcessRefreshKind) -> bool {
+    if p.name != name {
+        return true;
+    }
+    match fs::read_link(path.join("exe")) {
+        Ok(exe) if exe != p.exe => return true,
+        _ => {}
+    }
+    // If the command line wasn't retrieved before, there is nothing to compare it with.
+    refresh_kind.cmd() && !p.cmd_os.is_empty() && copy_from_file(&path.join("cmdline")) != p.cmd_os
+}
+
+/// Clears the information retrieved by [`update_process_info`] so that none of it is left over
+/// from the program the process ran before `exec` if `refresh_kind` doesn't ask for it.
+fn clear_process_info(p: &mut Process) {
+    p.cmd.clear();
+    p.cmd_os.clear();
+    p.exe = PathBuf::new();
+    p.environ.clear();
+    p.environ_os.clear();
+    p.cwd = PathBuf::new();
+    p.root = PathBuf::new();
+    p.namespaces = Namespaces::default();
+    p.container = None;
+    p.systemd_unit = None;
+    p.systemd_slice = None;
+    p.cgroups.clear();
+}
+
+/// Retrieves the information which is only updated when the process is listed or executes
+/// another program: the command line, the executable path, the environment, the current working
+/// directory, the root directory, the namespaces and the cgroup based information (which are
+/// both also updated at every refresh).
+pub(crate) fn update_process_info(p: &mut Process, path: &Path, refresh_kind: ProcessRefreshKind) {
+    let mut tmp = PathBuf::from(path);
+    if refresh_kind.cmd() {
+        tmp.push("cmdline");
+        p.cmd_os = copy_from_file(&tmp);
+        p.cmd = to_strings(&p.cmd_os);
+        tmp.pop();
+    }
+    tmp.push("exe");
+    match tmp.read_link() {
+        Ok(exe_path) => {
+            p.exe = exe_path;
+        }
+        Err(_) => {
+            p.exe = if let Some(cmd) = p.cmd_os.first() {
+                PathBuf::from(cmd)
+            } else {
+                PathBuf::new()
+            };
+        }
+    }
+    tmp.pop();
+    if refresh_kind.environ() {
+        tmp.push("environ");
+        p.environ_os = copy_from_file(&tmp);
+        p.environ = to_strings(&p.environ_os);
+        tmp.pop();
+    }
+    if refresh_kind.cwd() {
+        tmp.push("cwd");
+        p.cwd = realpath(&tmp);
+        tmp.pop();
+    }
+    if refresh_kind.root() {
+        tmp.push("root");
+        p.root = realpath(&tmp);
+        tmp.pop();
+    }
+    if refresh_kind.namespaces() {
+        tmp.push("ns");
+        p.namespaces = get_namespaces(&tmp);
+        tmp.pop();
+    }
+    if refresh_kind.cgroup() {
+        update_cgroup_info(p, path);
+    }
+}
+
+fn update_cgroup_info(p: &mut Process, path: &Path) {
+    let data = get_all_data(path.join("cgroup"), 1024).unwrap_or_default();
+    let cgroups = parse_cgroup_paths(&data);
+    p.container = get_container(&cgroups);
+    p.cgroups = cgroups.into_iter().map(|path| path.to_owned()).collect();
+    let (unit, slice) = parse_systemd_cgroup_path(&data)
+        .map(get_systemd_unit_and_slice)
+        .unwrap_or_default();
+    p.systemd_unit = unit.map(|unit| unit.to_owned());
+    p.systemd_slice = slice.map(|slice| slice.to_owned());
+}
+
+fn get_namespaces(path: &Path) -> Namespaces {
+    let get = |name: &str| {
+        fs::read_link(path.join(name))
+            .ok()
+            .and_then(|link| _get_namespace_id(link.to_str()?))
+    };
+    Namespaces {
+        cgroup: get("cgroup"),
+        ipc: get("ipc"),
+        mnt: get("mnt"),
+        net: get("net"),
+        pid: get("pid"),
+        user: get("user"),
+        uts: get("uts"),
+    }
+}
+
+// The links look like `net:[4026531840]`.
+fn _get_namespace_id(link: &str) -> Option<u64> {
+    let start = link.find(":[")? + 2;
+    link[start..].strip_suffix(']')?.parse().ok()
+}
+
+// `NSpid` contains the pid of the process in each of the nested pid namespaces it belongs to,
+// the last one being its own.
+fn _get_namespace_pid(status_data: &str) -> Option<Pid> {
+    status_data
+        .lines()
+        .find_map(|line| line.strip_prefix("NSpid:"))?
+        .split_whitespace()
+        .last()?
+        .parse()
+        .ok()
+}
+
+fn parse_part<T: FromStr + Default>(parts: &[&str], index: usize) -> T {
+    parts
+        .get(index)
+        .and_then(|part| T::from_str(part).ok())
+        .unwrap_or_default()
+}
+
+fn update_scheduling_and_page_faults(entry: &mut Process, parts: &[&str]) {
+    entry.scheduling = SchedulingInfo {
+        priority: parse_part(parts, 17),
+        nice: parse_part(parts, 18),
+        processor: parse_part(parts, 38),
+        rt_priority: parse_part(parts, 39),
+        policy: SchedulingPolicy::from(parse_part::<u32>(parts, 40)),
+    };
+    entry.old_minor_faults = entry.minor_faults;
+    entry.old_major_faults = entry.major_faults;
+    entry.minor_faults = parse_part(parts, 9);
+    entry.major_faults = parse_part(parts, 11);
+}
+
+fn update_session(entry: &mut Process, parts: &[&str]) {
+    let non_zero = |pid: Pid| if pid > 0 { Some(pid) } else { None };
+    entry.process_group_id = non_zero(parse_part(parts, 4));
+    entry.session_id = non_zero(parse_part(parts, 5));
+    let tty_nr = parse_part::<i32>(parts, 6) as u32;
+    if tty_nr != entry.tty_nr || (entry.tty.is_none() && tty_nr != 0) {
+        entry.tty_nr = tty_nr;
+        entry.tty = get_tty_path(tty_nr);
+    }
+}
+
+fn get_tty_path(tty_nr: u32) -> Option<PathBuf> {
+    if tty_nr == 0 {
+        return None;
+    }
+    let (major, minor) = _split_tty_nr(tty_nr);
+    _get_known_tty_path(major, minor).or_else(|| {
+        // Otherwise we ask sysfs for the name of the device.
+        let uevent = get_all_data(format!("/sys/dev/char/{}:{}/uevent", major, minor), 256).ok()?;
+        let name = uevent.lines().find_map(|l| l.strip_prefix("DEVNAME="))?;
+        Some(Path::new("/dev").join(name))
+    })
+}
+
+// Same encoding as `dev_t`: the minor number is split around the major one.
+fn _split_tty_nr(tty_nr: u32) -> (u32, u32) {
+    let major = (tty_nr >> 8) & 0xfff;
+    let minor = (tty_nr & 0xff) | ((tty_nr >> 12) & 0xfff00);
+    (major, minor)
+}
+
+// Returns the path of the terminals with a fixed device number (see the `devices.txt` file of
+// the kernel documentation).
+fn _get_known_tty_path(major: u32, minor: u32) -> Option<PathBuf> {
+    let path = match (major, minor) {
+        (136..=143, _) => format!("/dev/pts/{}", (major - 136) * 256 + minor),
+        (4, 0..=63) => format!("/dev/tty{}", minor),
+        (4, _) => format!("/dev/ttyS{}", minor - 64),
+        (5, 0) => "/dev/tty".to_owned(),
+        (5, 1) => "/dev/console".to_owned(),
+        _ => return None,
+    };
+    Some(PathBuf::from(path))
+}
+
+fn update_context_switches(entry: &mut Process, status_data: &str) {
+    let (voluntary, involuntary) = _get_context_switches(status_data);
+    entry.old_voluntary_ctxt_switches = entry.voluntary_ctxt_switches;
+    entry.old_involuntary_ctxt_switches = entry.involuntary_ctxt_switches;
+    entry.voluntary_ctxt_switches = voluntary;
+    entry.involuntary_ctxt_switches = involuntary;
+}
+
 #[allow(clippy::too_many_arguments)]
 fn update_time_and_memory(
     path: &Path,
@@ -502,8 +1153,9 @@ fn update_time_and_memory(
     parent_memory: u64,
     parent_virtual_memory: u64,
     pid: Pid,
-    uptime: u64,
-    now: u64,
+    uptime: Duration,
+    now: Duration,
+    refresh_kind: ProcessRefreshKind,
 ) {
     {
         // rss
@@ -521,8 +1173,23 @@ fn update_time_and_memory(
             u64::from_str(parts[13]).unwrap_or(0),
             u64::from_str(parts[14]).unwrap_or(0),
         );
+        update_scheduling_and_page_faults(entry, parts);
+        update_session(entry, parts);
+    }
+    if refresh_kind.tasks() {
+        refresh_procs(
+            entry,
+            &path.join("task"),
+            page_size_kb,
+            pid,
+            uptime,
+            now,
+            refresh_kind,
+        );
+    } else {
+        // Otherwise the tasks would keep the values of the last refresh which listed them.
+        entry.tasks.clear();
     }
-    refresh_procs(entry, &path.join("task"), page_size_kb, pid, uptime, now);
 }
 
 pub(crate) fn refresh_procs(
@@ -530,8 +1197,9 @@ pub(crate) fn refresh_procs(
     path: &Path,
     page_size_kb: u64,
     pid: Pid,
-    uptime: u64,
-    now: u64,
+    uptime: Duration,
+    now: Duration,
+    refresh_kind: ProcessRefreshKind,
 ) -> bool {
     if let Ok(d) = fs::read_dir(path) {
         let folders = d
@@ -564,6 +1232,7 @@ pub(crate) fn refresh_procs(
                         pid,
                         uptime,
                         now,
+                        refresh_kind,
                     ) {
                         p
                     } else {
@@ -576,9 +1245,15 @@ pub(crate) fn refresh_procs(
             let new_tasks = folders
                 .iter()
                 .filter_map(|e| {
-                    if let Ok((p, pid)) =
-                        _get_process_data(e.as_path(), proc_list, page_size_kb, pid, uptime, now)
-                    {
+                    if let Ok((p, pid)) = _get_process_data(
+                        e.as_path(),
+                        proc_list,
+                        page_size_kb,
+                        pid,
+                        uptime,
+                        now,
+                        refresh_kind,
+                    ) {
                         updated_pids.push(pid);
                         p
                     } else {
@@ -602,37 +1277,49 @@ pub(crate) fn refresh_procs(
     }
 }
 
-fn copy_from_file(entry: &Path) -> Vec<String> {
-    match File::open(entry) {
-        Ok(mut f) => {
-            let mut data = vec![0; 16_384];
-
-            if let Ok(size) = f.read(&mut data) {
-                data.truncate(size);
-                let mut out = Vec::with_capacity(20);
-                let mut start = 0;
-                for (pos, x) in data.iter().enumerate() {
-                    if *x == 0 {
-                        if pos - start >= 1 {
-                            if let Ok(s) =
-                                std::str::from_utf8(&data[start..pos]).map(|x| x.trim().to_owned())
-                            {
-                                out.push(s);
-                            }
-                        }
-                        start = pos + 1; // to keeping prevent '\0'
-                    }
-                }
-                out
-            } else {
-                Vec::new()
-            }
+/// Reads a file containing NUL-separated values, like `cmdline` or `environ`.
+fn copy_from_file(entry: &Path) -> Vec<OsString> {
+    let mut data = Vec::new();
+    if File::open(entry)
+        .and_then(|mut f| f.read_to_end(&mut data))
+        .is_err()
+    {
+        return Vec::new();
+    }
+    // The last value is followed by a NUL character as well.
+    if data.last() == Some(&0) {
+        data.pop();
+    }
+    if data.is_empty() {
+        return Vec::new();
+    }
+    data.split(|&c| c == 0)
+        .map(|value| OsStr::from_bytes(value).to_os_string())
+        .collect()
+}
+
+fn to_strings(values: &[OsString]) -> Vec<String> {
+    values
+        .iter()
+        .map(|value| value.to_string_lossy().trim().to_owned())
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+fn _get_context_switches(status_data: &str) -> (u64, u64) {
+    let mut voluntary = 0;
+    let mut involuntary = 0;
+    for line in status_data.lines() {
+        if let Some(value) = line.strip_prefix("voluntary_ctxt_switches:") {
+            voluntary = value.trim().parse().unwrap_or(0);
+        } else if let Some(value) = line.strip_prefix("nonvoluntary_ctxt_switches:") {
+            involuntary = value.trim().parse().unwrap_or(0);
         }
-        Err(_) => Vec::new(),
     }
+    (voluntary, involuntary)
 }
 
-fn _get_uid_and_gid(status_data: String) -> Option<(uid_t, gid_t)> {
+fn _get_uid_and_gid(status_data: &str) -> Option<(uid_t, gid_t)> {
     // We're only interested in the lines starting with Uid: and Gid:
     // here. From these lines, we're looking at the second entry to get
     // the effective u/gid.
@@ -709,3 +1396,58 @@ fn parse_stat_file(data: &str) -> Result<Vec<&str>, ()> {
     }
     Ok(parts)
 }
+
+#[cfg(test)]
+mod test {
+    use super::{_get_known_tty_path, _get_namespace_id, _get_namespace_pid, _split_tty_nr};
+    use std::path::PathBuf;
+
+    #[test]
+    fn check_tty_nr() {
+        assert_eq!(_split_tty_nr(34816), (136, 0));
+        assert_eq!(_split_tty_nr(34821), (136, 5));
+        assert_eq!(_split_tty_nr(1025), (4, 1));
+        // Minor numbers above 255.
+        assert_eq!(_split_tty_nr((1 << 20) | (136 << 8) | 2), (136, 258));
+    }
+
+    #[test]
+    fn check_known_tty_path() {
+        let path = |major, minor| _get_known_tty_path(major, minor);
+        assert_eq!(path(136, 5), Some(PathBuf::from("/dev/pts/5")));
+        assert_eq!(path(137, 1), Some(PathBuf::from("/dev/pts/257")));
+        assert_eq!(path(4, 1), Some(PathBuf::from("/dev/tty1")));
+        assert_eq!(path(4, 64), Some(PathBuf::from("/dev/ttyS0")));
+        assert_eq!(path(5, 1), Some(PathBuf::from("/dev/console")));
+        assert_eq!(path(188, 0), None);
+    }
+
+    #[test]
+    fn check_namespace_id() {
+        assert_eq!(_get_namespace_id("net:[4026531840]"), Some(4026531840));
+        assert_eq!(
+            _get_namespace_id("pid_for_children:[4026531836]"),
+            Some(4026531836)
+        );
+        assert_eq!(_get_namespace_id("net:[4026531840"), None);
+        assert_eq!(_get_namespace_id("/proc/1/ns/net"), None);
+    }
+
+    #[test]
+    fn check_namespace_pid() {
+        assert_eq!(
+            _get_namespace_pid("Name:\tbash\nTgid:\t1234\nNSpid:\t1234\nPPid:\t1\n"),
+            Some(1234)
+        );
+        // Process in nested pid namespaces.
+        assert_eq!(
+            _get_namespace_pid("Tgid:\t1234\nNSpid:\t1234\t56\t7\nPPid:\t1\n"),
+            Some(7)
+        );
+        // Kernels older than 4.1 don't have `NSpid`.
+        assert_eq!(
+            _get_namespace_pid("Tgid:\t1234\nPid:\t1234\nPPid:\t1\n"),
+            None
+        );
+    }
+}
@@ -6,11 +6,14 @@
 
 #![allow(clippy::too_many_arguments)]
 
-use std::collections::HashSet;
-use std::fs::File;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
 use std::io::Read;
+use std::path::Path;
+use std::time::Duration;
 
-use crate::ProcessorExt;
+use crate::sys::utils::get_all_data;
+use crate::{CpuInfo, CpuVulnerability, FrequencyScaling, FrequencyState, ProcessorExt};
 
 /// Struct containing values to compute a CPU usage.
 #[derive(Clone, Copy)]
@@ -219,6 +222,12 @@ impl ProcessorExt for Processor {
     fn brand(&self) -> &str {
         &self.brand
     }
+
+    fn frequency_scaling(&self) -> Option<FrequencyScaling> {
+        // The global processor is named "cpu".
+        let index = self.name.strip_prefix("cpu")?.parse().ok()?;
+        get_frequency_scaling(index)
+    }
 }
 
 pub fn get_raw_times(p: &Processor) -> (u64, u64) {
@@ -248,19 +257,87 @@ pub fn get_cpu_frequency(cpu_core_index: usize) -> u64 {
     {
         return 0;
     }
-    let find_cpu_mhz = s.split('\n').find(|line| {
-        line.starts_with("cpu MHz\t")
-            || line.starts_with("BogoMIPS")
-            || line.starts_with("clock\t")
-            || line.starts_with("bogomips per cpu")
-    });
+    // BogoMIPS aren't a frequency so they aren't used as fallback.
+    let find_cpu_mhz = s
+        .split('\n')
+        .find(|line| line.starts_with("cpu MHz\t") || line.starts_with("clock\t"));
     find_cpu_mhz
-        .and_then(|line| line.split(':').last())
+        .and_then(|line| line.split(':').next_back())
         .and_then(|val| val.replace("MHz", "").trim().parse::<f64>().ok())
         .map(|speed| speed as u64)
         .unwrap_or_default()
 }
 
+pub(crate) fn get_frequency_scaling(cpu: usize) -> Option<FrequencyScaling> {
+    let path = Path::new("/sys/devices/system/cpu").join(format!("cpu{}/cpufreq", cpu));
+    if !path.exists() {
+        return None;
+    }
+    let read = |name: &str| {
+        get_all_data(path.join(name), 1_024)
+            .ok()
+            .map(|v| v.trim().to_owned())
+    };
+    // The frequencies are in kHz.
+    let read_frequency = |name: &str| {
+        read(name)
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(|freq| freq / 1_000)
+    };
+    let (time_in_state, transitions) = match read("stats/time_in_state") {
+        Some(data) => (
+            parse_time_in_state(&data),
+            read("stats/total_trans").and_then(|v| v.parse().ok()),
+        ),
+        None => (Vec::new(), None),
+    };
+    Some(FrequencyScaling {
+        driver: read("scaling_driver"),
+        current_frequency: read_frequency("scaling_cur_freq"),
+        min_frequency: read_frequency("cpuinfo_min_freq"),
+        max_frequency: read_frequency("cpuinfo_max_freq"),
+        base_frequency: read_frequency("base_frequency"),
+        scaling_min_frequency: read_frequency("scaling_min_freq"),
+        scaling_max_frequency: read_frequency("scaling_max_freq"),
+        governor: read("scaling_governor"),
+        available_governors: read("scaling_available_governors")
+            .map(|v| v.split_whitespace().map(str::to_owned).collect())
+            .unwrap_or_default(),
+        energy_performance_preference: read("energy_performance_preference"),
+        boost: get_boost(),
+        time_in_state,
+        transitions,
+    })
+}
+
+fn get_boost() -> Option<bool> {
+    let read = |path: &str| {
+        get_all_data(path, 16).ok().and_then(|v| match v.trim() {
+            "0" => Some(false),
+            "1" => Some(true),
+            _ => None,
+        })
+    };
+    read("/sys/devices/system/cpu/cpufreq/boost")
+        .or_else(|| read("/sys/devices/system/cpu/intel_pstate/no_turbo").map(|no_turbo| !no_turbo))
+}
+
+/// Parses `cpufreq/stats/time_in_state`: one line per frequency (in kHz) with the time spent
+/// at it (in 10ms units).
+fn parse_time_in_state(data: &str) -> Vec<FrequencyState> {
+    data.lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let frequency = parts.next()?.parse::<u64>().ok()?;
+            let time = parts.next()?.parse::<u64>().ok()?;
+            Some(FrequencyState {
+                frequency: frequency / 1_000,
+                time: Duration::from_millis(time.saturating_mul(10)),
+            })
+        })
+        .collect()
+}
+
 pub fn get_physical_core_count() -> Option<usize> {
     let mut s = String::new();
     if let Err(_e) = File::open("/proc/cpuinfo").and_then(|mut f| f.read_to_string(&mut s)) {
@@ -297,35 +374,259 @@ pub fn get_physical_core_count() -> Option<usize> {
 
 /// Returns the brand/vendor string for the first CPU (which should be the same for all CPUs).
 pub fn get_vendor_id_and_brand() -> (String, String) {
+    match get_cpu_info() {
+        Some(info) => (info.vendor_id, info.brand),
+        None => (String::new(), String::new()),
+    }
+}
+
+pub(crate) fn get_cpu_info() -> Option<CpuInfo> {
     let mut s = String::new();
-    if File::open("/proc/cpuinfo")
-        .and_then(|mut f| f.read_to_string(&mut s))
-        .is_err()
-    {
-        return (String::new(), String::new());
+    if let Err(_e) = File::open("/proc/cpuinfo").and_then(|mut f| f.read_to_string(&mut s)) {
+        sysinfo_debug!("Cannot read `/proc/cpuinfo` file: {:?}", _e);
+        return None;
     }
+    Some(parse_cpu_info(&s))
+}
 
-    fn get_value(s: &str) -> String {
-        s.split(':')
-            .last()
-            .map(|x| x.trim().to_owned())
-            .unwrap_or_default()
+/// Parses `/proc/cpuinfo`. For each field, the first value found is used (so the one of the
+/// first CPU).
+fn parse_cpu_info(data: &str) -> CpuInfo {
+    fn set<T>(field: &mut Option<T>, value: Option<T>) {
+        if field.is_none() {
+            *field = value;
+        }
     }
 
+    let mut info = CpuInfo::default();
     let mut vendor_id = None;
     let mut brand = None;
+    let mut flags = None;
+    let mut bugs = None;
+    for line in data.lines() {
+        let mut parts = line.splitn(2, ':');
+        let (key, value) = match (parts.next(), parts.next()) {
+            (Some(key), Some(value)) => (key.trim(), value.trim()),
+            _ => continue,
+        };
+        match key {
+            "vendor_id" => set(&mut vendor_id, Some(value)),
+            "model name" => set(&mut brand, Some(value)),
+            "cpu family" => set(&mut info.family, value.parse().ok()),
+            "model" => set(&mut info.model, value.parse().ok()),
+            "stepping" => set(&mut info.stepping, value.parse().ok()),
+            "microcode" => set(
+                &mut info.microcode,
+                u64::from_str_radix(value.trim_start_matches("0x"), 16).ok(),
+            ),
+            // "Features" is used on ARM.
+            "flags" | "Features" => set(&mut flags, Some(value)),
+            "bugs" => set(&mut bugs, Some(value)),
+            "bogomips" | "BogoMIPS" => set(&mut info.bogomips, value.parse().ok()),
+            "address sizes" => {
+                // "46 bits physical, 57 bits virtual"
+                for size in value.split(',') {
+                    let mut parts = size.split_whitespace();
+                    let bits = parts.next().and_then(|v| v.parse().ok());
+                    match parts.nth(1) {
+                        Some("physical") => set(&mut info.physical_address_bits, bits),
+                        Some("virtual") => set(&mut info.virtual_address_bits, bits),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    let to_set = |list: Option<&str>| {
+        list.map(|list| list.split_whitespace().map(str::to_owned).collect())
+            .unwrap_or_default()
+    };
+    info.vendor_id = vendor_id.unwrap_or_default().to_owned();
+    info.brand = brand.unwrap_or_default().to_owned();
+    info.flags = to_set(flags);
+    info.bugs = to_set(bugs);
+    info
+}
+
+pub(crate) fn get_cpu_vulnerabilities() -> HashMap<String, CpuVulnerability> {
+    let entries = match fs::read_dir("/sys/devices/system/cpu/vulnerabilities") {
+        Ok(entries) => entries,
+        Err(_) => return HashMap::new(),
+    };
+    entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let name = entry.file_name().into_string().ok()?;
+            let status = get_all_data(entry.path(), 256).ok()?;
+            Some((name, parse_cpu_vulnerability(&status)))
+        })
+        .collect()
+}
 
-    for it in s.split('\n') {
-        if it.starts_with("vendor_id\t") {
-            vendor_id = Some(get_value(it));
-        } else if it.starts_with("model name\t") {
-            brand = Some(get_value(it));
-        } else {
-            continue;
+/// Parses a file of `/sys/devices/system/cpu/vulnerabilities`, like `Not affected`,
+/// `Mitigation: PTI` or `Vulnerable: Clear CPU buffers attempted, no microcode`.
+fn parse_cpu_vulnerability(status: &str) -> CpuVulnerability {
+    let status = status.trim();
+    // It can be prefixed with a single word, like in "KVM: Mitigation: VMX disabled".
+    let state = match status.find(": ") {
+        Some(pos)
+            if !status[..pos].contains(|c: char| c.is_whitespace() || c == ',')
+                && !matches!(&status[..pos], "Mitigation" | "Vulnerable" | "Unknown") =>
+        {
+            &status[pos + 2..]
         }
-        if brand.is_some() && vendor_id.is_some() {
-            break;
+        _ => status,
+    };
+    if state == "Not affected" {
+        CpuVulnerability::NotAffected
+    } else if let Some(details) = state.strip_prefix("Mitigation:") {
+        CpuVulnerability::Mitigated(details.trim().to_owned())
+    } else if state == "Processor vulnerable" {
+        CpuVulnerability::Vulnerable(String::new())
+    } else if let Some(details) = state.strip_prefix("Vulnerable") {
+        // The details can be separated with a comma, like in
+        // "Vulnerable, IBPB: disabled, STIBP: disabled".
+        match details.chars().next() {
+            None => CpuVulnerability::Vulnerable(String::new()),
+            Some(',') | Some(';') | Some(':') => {
+                CpuVulnerability::Vulnerable(details[1..].trim().to_owned())
+            }
+            _ => CpuVulnerability::Unknown(status.to_owned()),
         }
+    } else {
+        CpuVulnerability::Unknown(status.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_cpu_info, parse_cpu_vulnerability, parse_time_in_state};
+    use crate::{CpuVulnerability, FrequencyState};
+    use std::time::Duration;
+
+    #[test]
+    fn check_parse_time_in_state() {
+        assert_eq!(
+            parse_time_in_state("3600000 1203\n2800000 45\n800000 987654\n"),
+            vec![
+                FrequencyState {
+                    frequency: 3600,
+                    time: Duration::from_millis(12030),
+                },
+                FrequencyState {
+                    frequency: 2800,
+                    time: Duration::from_millis(450),
+                },
+                FrequencyState {
+                    frequency: 800,
+                    time: Duration::from_millis(9876540),
+                },
+            ]
+        );
+        assert_eq!(parse_time_in_state(""), Vec::new());
+    }
+
+    #[test]
+    fn check_parse_cpu_info() {
+        let info = parse_cpu_info(
+            "processor\t: 0\n\
+             vendor_id\t: GenuineIntel\n\
+             cpu family\t: 6\n\
+             model\t\t: 207\n\
+             model name\t: Intel(R) Xeon(R) Processor\n\
+             stepping\t: 2\n\
+             microcode\t: 0x2b000571\n\
+             cpu MHz\t\t: 2100.000\n\
+             flags\t\t: fpu sse2 avx2 aes avx512f\n\
+             bugs\t\t: spectre_v1 spectre_v2\n\
+             bogomips\t: 4200.00\n\
+             address sizes\t: 46 bits physical, 57 bits virtual\n\
+             power management:\n\
+             \n\
+             processor\t: 1\n\
+             vendor_id\t: GenuineIntel\n\
+             stepping\t: 3\n\
+             flags\t\t: fpu\n",
+        );
+        assert_eq!(info.vendor_id, "GenuineIntel");
+        assert_eq!(info.brand, "Intel(R) Xeon(R) Processor");
+        assert_eq!(info.family, Some(6));
+        assert_eq!(info.model, Some(207));
+        assert_eq!(info.stepping, Some(2));
+        assert_eq!(info.microcode, Some(0x2b000571));
+        assert_eq!(info.flags.len(), 5);
+        assert!(info.has_flag("avx2") && info.has_flag("avx512f"));
+        assert!(!info.has_flag("avx512"));
+        assert!(info.bugs.contains("spectre_v2"));
+        assert_eq!(info.bogomips, Some(4200.));
+        assert_eq!(info.physical_address_bits, Some(46));
+        assert_eq!(info.virtual_address_bits, Some(57));
+
+        // ARM.
+        let info = parse_cpu_info(
+            "processor\t: 0\n\
+             BogoMIPS\t: 48.00\n\
+             Features\t: fp asimd evtstrm aes crc32\n\
+             CPU implementer\t: 0x41\n",
+        );
+        assert_eq!(info.vendor_id, "");
+        assert!(info.has_flag("asimd"));
+        assert_eq!(info.bogomips, Some(48.));
+        assert_eq!(info.family, None);
+    }
+
+    #[test]
+    fn check_parse_cpu_vulnerability() {
+        assert_eq!(
+            parse_cpu_vulnerability("Not affected\n"),
+            CpuVulnerability::NotAffected
+        );
+        assert_eq!(
+            parse_cpu_vulnerability("Mitigation: PTI\n"),
+            CpuVulnerability::Mitigated("PTI".to_owned())
+        );
+        assert_eq!(
+            parse_cpu_vulnerability("KVM: Mitigation: VMX disabled\n"),
+            CpuVulnerability::Mitigated("VMX disabled".to_owned())
+        );
+        assert_eq!(
+            parse_cpu_vulnerability("Vulnerable\n"),
+            CpuVulnerability::Vulnerable(String::new())
+        );
+        assert_eq!(
+            parse_cpu_vulnerability("Vulnerable: Clear CPU buffers attempted, no microcode\n"),
+            CpuVulnerability::Vulnerable("Clear CPU buffers attempted, no microcode".to_owned())
+        );
+        assert_eq!(
+            parse_cpu_vulnerability("Vulnerable, IBPB: disabled, STIBP: disabled\n"),
+            CpuVulnerability::Vulnerable("IBPB: disabled, STIBP: disabled".to_owned())
+        );
+        assert_eq!(
+            parse_cpu_vulnerability("KVM: Vulnerable\n"),
+            CpuVulnerability::Vulnerable(String::new())
+        );
+        assert_eq!(
+            parse_cpu_vulnerability("Vulnerable; SMT vulnerable\n"),
+            CpuVulnerability::Vulnerable("SMT vulnerable".to_owned())
+        );
+        assert_eq!(
+            parse_cpu_vulnerability("Vulnerable: eIBRS with unprivileged eBPF; Mitigation: PTI\n"),
+            CpuVulnerability::Vulnerable(
+                "eIBRS with unprivileged eBPF; Mitigation: PTI".to_owned()
+            )
+        );
+        assert_eq!(
+            parse_cpu_vulnerability("Processor vulnerable\n"),
+            CpuVulnerability::Vulnerable(String::new())
+        );
+        assert_eq!(
+            parse_cpu_vulnerability("Unknown: Dependent on hypervisor status\n"),
+            CpuVulnerability::Unknown("Unknown: Dependent on hypervisor status".to_owned())
+        );
+        assert_eq!(
+            parse_cpu_vulnerability("Unknown: No mitigations\n"),
+            CpuVulnerability::Unknown("Unknown: No mitigations".to_owned())
+        );
     }
-    (vendor_id.unwrap_or_default(), brand.unwrap_or_default())
 }
@@ -0,0 +1,162 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::sys::utils::get_all_data;
+use crate::{SwapDevice, SwapKind, ZramStats};
+
+use std::cmp::Reverse;
+use std::path::{Path, PathBuf};
+
+pub(crate) fn get_swap_devices() -> Vec<SwapDevice> {
+    let mut devices = match get_all_data("/proc/swaps", 4_096) {
+        Ok(data) => parse_swaps(&data),
+        Err(_) => return Vec::new(),
+    };
+    for device in devices.iter_mut() {
+        if device.kind == SwapKind::Zram {
+            device.zram = get_zram_stats(&device.path);
+        }
+    }
+    devices
+}
+
+/// Parses `/proc/swaps`, which looks like:
+///
+/// ```text
+/// Filename                                Type            Size            Used            Priority
+/// /dev/zram0                              partition       4194300         1024            100
+/// /swapfile                               file            2097148         0               -2
+/// ```
+fn parse_swaps(data: &str) -> Vec<SwapDevice> {
+    let mut devices = data
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let path = unescape_path(parts.next()?);
+            let kind = match parts.next()? {
+                _ if path.starts_with("/dev/zram") => SwapKind::Zram,
+                "partition" => SwapKind::Partition,
+                "file" => SwapKind::File,
+                _ => SwapKind::Unknown,
+            };
+            Some(SwapDevice {
+                path: PathBuf::from(path),
+                kind,
+                size: parts.next()?.parse().ok()?,
+                used: parts.next()?.parse().ok()?,
+                priority: parts.next()?.parse().ok()?,
+                zram: None,
+            })
+        })
+        .collect::<Vec<_>>();
+    devices.sort_by_key(|device| Reverse(device.priority));
+    devices
+}
+
+/// The whitespaces and backslashes of the paths are escaped with their octal value (like
+/// `\040` for a space).
+fn unescape_path(path: &str) -> String {
+    let mut unescaped = String::with_capacity(path.len());
+    let mut rest = path;
+    while let Some(pos) = rest.find('\\') {
+        unescaped.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        match rest
+            .get(1..4)
+            .and_then(|code| u8::from_str_radix(code, 8).ok())
+        {
+            Some(c) => {
+                unescaped.push(c as char);
+                rest = &rest[4..];
+            }
+            None => {
+                unescaped.push('\\');
+                rest = &rest[1..];
+            }
+        }
+    }
+    unescaped.push_str(rest);
+    unescaped
+}
+
+fn get_zram_stats(path: &Path) -> Option<ZramStats> {
+    let name = path.file_name()?.to_str()?;
+    let data = get_all_data(Path::new("/sys/block").join(name).join("mm_stat"), 256).ok()?;
+    parse_zram_mm_stat(&data)
+}
+
+/// Parses the `mm_stat` file of a zram device (available since Linux 4.1). The first three
+/// values are the original data size, the compressed data size and the total memory used, in
+/// bytes.
+fn parse_zram_mm_stat(data: &str) -> Option<ZramStats> {
+    let mut values = data.split_whitespace().map(|v| v.parse::<u64>().ok());
+    Some(ZramStats {
+        original_size: values.next()?? / 1_024,
+        compressed_size: values.next()?? / 1_024,
+        memory_used: values.next()?? / 1_024,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_swaps, parse_zram_mm_stat, unescape_path};
+    use crate::{SwapDevice, SwapKind, ZramStats};
+    use std::path::PathBuf;
+
+    fn device(path: &str, kind: SwapKind, size: u64, used: u64, priority: i32) -> SwapDevice {
+        SwapDevice {
+            path: PathBuf::from(path),
+            kind,
+            size,
+            used,
+            priority,
+            zram: None,
+        }
+    }
+
+    #[test]
+    fn check_parse_swaps() {
+        assert_eq!(
+            parse_swaps(
+                "Filename\t\t\t\tType\t\tSize\t\tUsed\t\tPriority\n\
+                 /swapfile                               file\t\t2097148\t\t0\t\t-2\n\
+                 /dev/zram0                              partition\t4194300\t\t1024\t\t100\n\
+                 /dev/sda2                               partition\t8388604\t\t0\t\t10\n"
+            ),
+            vec![
+                device("/dev/zram0", SwapKind::Zram, 4194300, 1024, 100),
+                device("/dev/sda2", SwapKind::Partition, 8388604, 0, 10),
+                device("/swapfile", SwapKind::File, 2097148, 0, -2),
+            ]
+        );
+        assert_eq!(
+            parse_swaps("Filename\t\t\t\tType\t\tSize\t\tUsed\t\tPriority\n"),
+            Vec::new()
+        );
+    }
+
+    #[test]
+    fn check_unescape_path() {
+        assert_eq!(unescape_path("/swapfile"), "/swapfile");
+        assert_eq!(unescape_path("/my\\040swap\\011file"), "/my swap\tfile");
+        assert_eq!(unescape_path("/back\\134slash"), "/back\\slash");
+        assert_eq!(unescape_path("/trailing\\"), "/trailing\\");
+    }
+
+    #[test]
+    fn check_parse_zram_mm_stat() {
+        assert_eq!(
+            parse_zram_mm_stat("  4194304  1048576  1310720        0  1310720      12        0\n"),
+            Some(ZramStats {
+                original_size: 4096,
+                compressed_size: 1024,
+                memory_used: 1280,
+            })
+        );
+        assert_eq!(parse_zram_mm_stat("4194304\n"), None);
+    }
+}
@@ -6,10 +6,19 @@
 
 use crate::sys::component::{self, Component};
 use crate::sys::disk;
+use crate::sys::hugepages::get_hugepages;
+use crate::sys::interrupts::{parse_interrupts, update_interrupts};
+use crate::sys::network::get_tcp_sockets_counters;
 use crate::sys::process::*;
 use crate::sys::processor::*;
+use crate::sys::swap::get_swap_devices;
+use crate::sys::topology::{get_cpu_topology, get_numa_nodes};
 use crate::sys::utils::get_all_data;
-use crate::{Disk, LoadAvg, Networks, Pid, ProcessExt, RefreshKind, SystemExt, User};
+use crate::{
+    CpuInfo, CpuTopology, CpuVulnerability, Disk, HugePages, Interrupt, KernelLimits, KernelStats,
+    LoadAvg, Networks, NumaNode, Pid, Pressure, PressureStats, ProcessExt, ProcessFilter,
+    ProcessRefreshKind, RefreshKind, ResourcePressure, SwapDevice, SystemExt, User,
+};
 
 use libc::{self, c_char, sysconf, _SC_HOST_NAME_MAX, _SC_PAGESIZE};
 use std::collections::HashMap;
@@ -18,7 +27,7 @@ use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
 // This whole thing is to prevent having too many files open at once. It could be problematic
 // for processes using a lot of files and using sysinfo at the same time.
@@ -102,6 +111,23 @@ fn boot_time() -> u64 {
     }
 }
 
+/// Raw kernel activity counters, read from `/proc/stat` and `/proc/vmstat`.
+#[derive(Clone, Copy, Default)]
+struct KernelCounters {
+    ctxt: u64,
+    intr: u64,
+    processes: u64,
+    procs_running: u64,
+    procs_blocked: u64,
+    pgfault: u64,
+    pgmajfault: u64,
+    pswpin: u64,
+    pswpout: u64,
+    pgscan: u64,
+    pgsteal: u64,
+    oom_kill: u64,
+}
+
 /// Structs containing system's information.
 pub struct System {
     process_list: Process,
@@ -121,9 +147,88 @@ pub struct System {
     networks: Networks,
     users: Vec<User>,
     boot_time: u64,
+    old_kernel_counters: KernelCounters,
+    kernel_counters: KernelCounters,
+    interrupts: Vec<Interrupt>,
+    softirqs: Vec<Interrupt>,
 }
 
 impl System {
+    pub(crate) fn process_mut(&mut self, pid: Pid) -> Option<&mut Process> {
+        self.process_list.tasks.get_mut(&pid)
+    }
+
+    pub(crate) fn remove_process(&mut self, pid: Pid) -> Option<Process> {
+        self.process_list.tasks.remove(&pid)
+    }
+
+    /// Refreshes the process corresponding to `pid` without updating its CPU usage, which
+    /// requires to refresh the processors.
+    pub(crate) fn refresh_process_data(
+        &mut self,
+        pid: Pid,
+        refresh_kind: ProcessRefreshKind,
+    ) -> bool {
+        let found = match _get_process_data(
+            &Path::new("/proc/").join(pid.to_string()),
+            &mut self.process_list,
+            self.page_size_kb,
+            0,
+            get_uptime(),
+            get_duration_since_epoch(),
+            refresh_kind,
+        ) {
+            Ok((Some(p), pid)) => {
+                self.process_list.tasks.insert(pid, p);
+                true
+            }
+            Ok(_) => true,
+            Err(_) => false,
+        };
+        if found && refresh_kind.network_usage() {
+            self.refresh_network_usage(Some(pid));
+        }
+        found
+    }
+
+    /// Refreshes the processors once, then updates the CPU usage of the processes corresponding
+    /// to `pids`.
+    fn refresh_processes_cpu_usage(&mut self, pids: &[Pid]) {
+        if pids.is_empty() || self.processors.is_empty() {
+            return;
+        }
+        self.refresh_processors(Some(1));
+        let (new, old) = get_raw_times(&self.global_processor);
+        let total_time = (if old >= new { 1 } else { new - old }) as f32;
+
+        for pid in pids {
+            if let Some(p) = self.process_list.tasks.get_mut(pid) {
+                compute_cpu_usage(p, self.processors.len() as u64, total_time);
+            }
+        }
+    }
+
+    fn refresh_network_usage(&mut self, pid: Option<Pid>) {
+        let net_ns = std::fs::read_link("/proc/self/ns/net").ok();
+        let sockets = get_tcp_sockets_counters();
+        let update = |p: &mut Process| {
+            update_process_network_usage(
+                p,
+                &Path::new("/proc").join(p.pid.to_string()),
+                net_ns.as_deref(),
+                &sockets,
+            );
+        };
+        match pid {
+            Some(pid) => {
+                if let Some(p) = self.process_list.tasks.get_mut(&pid) {
+                    update(p);
+                }
+            }
+            None => self.process_list.tasks.values_mut().for_each(update),
+        }
+    }
+
     fn clear_procs(&mut self) {
         if !self.processors.is_empty() {
             let (new, old) = get_raw_times(&self.global_processor);
@@ -285,6 +390,10 @@ impl SystemExt for System {
             networks: Networks::new(),
             users: Vec::new(),
             boot_time: boot_time(),
+            old_kernel_counters: KernelCounters::default(),
+            kernel_counters: KernelCounters::default(),
+            interrupts: Vec::new(),
+            softirqs: Vec::new(),
         };
         if !refreshes.cpu() {
             s.refresh_processors(None); // We need the processors to be filled.
@@ -326,47 +435,67 @@ impl SystemExt for System {
         self.refresh_processors(None);
     }
 
-    fn refresh_processes(&mut self) {
-        let uptime = self.uptime();
+    fn refresh_processes_specifics(&mut self, refresh_kind: ProcessRefreshKind) {
+        let uptime = get_uptime();
         if refresh_procs(
             &mut self.process_list,
             Path::new("/proc"),
             self.page_size_kb,
             0,
             uptime,
-            get_secs_since_epoch(),
+            get_duration_since_epoch(),
+            refresh_kind,
         ) {
             self.clear_procs();
+            if refresh_kind.network_usage() {
+                self.refresh_network_usage(None);
+            }
         }
     }
 
-    fn refresh_process(&mut self, pid: Pid) -> bool {
-        let uptime = self.uptime();
-        let found = match _get_process_data(
-            &Path::new("/proc/").join(pid.to_string()),
-            &mut self.process_list,
-            self.page_size_kb,
-            0,
-            uptime,
-            get_secs_since_epoch(),
-        ) {
-            Ok((Some(p), pid)) => {
-                self.process_list.tasks.insert(pid, p);
-                true
-            }
-            Ok(_) => true,
-            Err(_) => false,
-        };
-        if found && !self.processors.is_empty() {
-            self.refresh_processors(Some(1));
-            let (new, old) = get_raw_times(&self.global_processor);
-            let total_time = (if old >= new { 1 } else { new - old }) as f32;
+    fn refresh_process_specifics(&mut self, pid: Pid, refresh_kind: ProcessRefreshKind) -> bool {
+        let found = self.refresh_process_data(pid, refresh_kind);
+        if found {
+            self.refresh_processes_cpu_usage(&[pid]);
+        }
+        found
+    }
 
-            if let Some(p) = self.process_list.tasks.get_mut(&pid) {
-                compute_cpu_usage(p, self.processors.len() as u64, total_time);
+    fn refresh_processes_matching(&mut self, filter: &ProcessFilter) -> Vec<Pid> {
+        let refresh_kind = filter.refresh_kind();
+        let pids = self
+            .processes_matching(filter)
+            .map(|p| p.pid())
+            .collect::<Vec<_>>();
+        let pids = pids
+            .into_iter()
+            .filter(|&pid| self.refresh_process_data(pid, refresh_kind))
+            .collect::<Vec<_>>();
+        self.refresh_processes_cpu_usage(&pids);
+        pids.into_iter()
+            .filter(|&pid| matches!(self.process(pid), Some(p) if filter.is_match(p)))
+            .collect()
+    }
+
+    fn reload_process_info(&mut self, pid: Pid, refresh_kind: ProcessRefreshKind) -> bool {
+        let start_time_ticks = self
+            .process_list
+            .tasks
+            .get(&pid)
+            .map(|p| p.start_time_ticks);
+        // We first refresh the process to be sure that the pid didn't get reused.
+        if !self.refresh_process_specifics(pid, refresh_kind) {
+            return false;
+        }
+        match self.process_list.tasks.get_mut(&pid) {
+            // A new process already got its information read when it was created.
+            Some(p) if start_time_ticks == Some(p.start_time_ticks) => {
+                update_process_info(p, &Path::new("/proc/").join(pid.to_string()), refresh_kind);
+                true
             }
+            Some(_) => true,
+            None => false,
         }
-        found
     }
 
     fn refresh_disks_list(&mut self) {
@@ -377,6 +506,27 @@ impl SystemExt for System {
         self.users = crate::linux::users::get_users_list();
     }
 
+    fn refresh_kernel_stats(&mut self) {
+        let mut counters = KernelCounters::default();
+        if let Ok(data) = get_all_data("/proc/stat", 16_384) {
+            parse_stat_counters(&data, &mut counters);
+        }
+        if let Ok(data) = get_all_data("/proc/vmstat", 16_384) {
+            parse_vmstat_counters(&data, &mut counters);
+        }
+        self.old_kernel_counters = self.kernel_counters;
+        self.kernel_counters = counters;
+    }
+
+    fn refresh_interrupts(&mut self) {
+        if let Ok(data) = get_all_data("/proc/interrupts", 16_384) {
+            update_interrupts(&mut self.interrupts, parse_interrupts(&data));
+        }
+        if let Ok(data) = get_all_data("/proc/softirqs", 4_096) {
+            update_interrupts(&mut self.softirqs, parse_interrupts(&data));
+        }
+    }
+
     // COMMON PART
     //
     // Need to be moved into a "common" file to avoid duplication.
@@ -409,6 +559,18 @@ impl SystemExt for System {
         get_physical_core_count()
     }
 
+    fn cpu_topology(&self) -> Option<CpuTopology> {
+        get_cpu_topology()
+    }
+
+    fn cpu_info(&self) -> Option<CpuInfo> {
+        get_cpu_info()
+    }
+
+    fn cpu_vulnerabilities(&self) -> HashMap<String, CpuVulnerability> {
+        get_cpu_vulnerabilities()
+    }
+
     fn total_memory(&self) -> u64 {
         self.mem_total
     }
@@ -442,6 +604,22 @@ impl SystemExt for System {
         self.swap_total - self.swap_free
     }
 
+    fn swap_devices(&self) -> Vec<SwapDevice> {
+        get_swap_devices()
+    }
+
+    fn hugepages(&self) -> HugePages {
+        get_hugepages()
+    }
+
+    fn numa_nodes(&self) -> Vec<NumaNode> {
+        get_numa_nodes()
+    }
+
+    fn kernel_limits(&self) -> KernelLimits {
+        get_kernel_limits()
+    }
+
     fn components(&self) -> &[Component] {
         &self.components
     }
@@ -459,12 +637,7 @@ impl SystemExt for System {
     }
 
     fn uptime(&self) -> u64 {
-        let content = get_all_data("/proc/uptime", 50).unwrap_or_default();
-        content
-            .split('.')
-            .next()
-            .and_then(|t| t.parse().ok())
-            .unwrap_or_default()
+        get_uptime().as_secs()
     }
 
     fn boot_time(&self) -> u64 {
@@ -492,6 +665,55 @@ impl SystemExt for System {
         }
     }
 
+    fn pressure(&self) -> Option<Pressure> {
+        get_pressure(Path::new("/proc/pressure"), "")
+    }
+
+    fn cgroup_pressure(&self, cgroup: &str) -> Option<Pressure> {
+        let cgroup = cgroup.trim_start_matches('/');
+        // In hybrid mode, the cgroup v2 hierarchy is mounted in a sub-directory.
+        ["/sys/fs/cgroup", "/sys/fs/cgroup/unified"]
+            .iter()
+            .find_map(|root| get_pressure(&Path::new(root).join(cgroup), ".pressure"))
+    }
+
+    fn kernel_stats(&self) -> KernelStats {
+        let new = &self.kernel_counters;
+        let old = &self.old_kernel_counters;
+        KernelStats {
+            total_context_switches: new.ctxt,
+            context_switches: new.ctxt.saturating_sub(old.ctxt),
+            total_interrupts: new.intr,
+            interrupts: new.intr.saturating_sub(old.intr),
+            total_forks: new.processes,
+            forks: new.processes.saturating_sub(old.processes),
+            procs_running: new.procs_running,
+            procs_blocked: new.procs_blocked,
+            total_page_faults: new.pgfault,
+            page_faults: new.pgfault.saturating_sub(old.pgfault),
+            total_major_page_faults: new.pgmajfault,
+            major_page_faults: new.pgmajfault.saturating_sub(old.pgmajfault),
+            total_swapped_in_pages: new.pswpin,
+            swapped_in_pages: new.pswpin.saturating_sub(old.pswpin),
+            total_swapped_out_pages: new.pswpout,
+            swapped_out_pages: new.pswpout.saturating_sub(old.pswpout),
+            total_scanned_pages: new.pgscan,
+            scanned_pages: new.pgscan.saturating_sub(old.pgscan),
+            total_reclaimed_pages: new.pgsteal,
+            reclaimed_pages: new.pgsteal.saturating_sub(old.pgsteal),
+            total_oom_kills: new.oom_kill,
+            oom_kills: new.oom_kill.saturating_sub(old.oom_kill),
+        }
+    }
+
+    fn interrupts(&self) -> &[Interrupt] {
+        &self.interrupts
+    }
+
+    fn softirqs(&self) -> &[Interrupt] {
+        &self.softirqs
+    }
+
     fn users(&self) -> &[User] {
         &self.users
     }
@@ -669,20 +891,231 @@ fn get_system_info_android(info: InfoType) -> Option<String> {
     }
 }
 
-fn get_secs_since_epoch() -> u64 {
+fn get_duration_since_epoch() -> Duration {
     match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
-        Ok(n) => n.as_secs(),
+        Ok(n) => n,
         _ => panic!("SystemTime before UNIX EPOCH!"),
     }
 }
 
+fn get_uptime() -> Duration {
+    let content = get_all_data("/proc/uptime", 50).unwrap_or_default();
+    content
+        .split_whitespace()
+        .next()
+        .and_then(|t| f64::from_str(t).ok())
+        .filter(|t| t.is_finite() && *t >= 0.)
+        .map(Duration::from_secs_f64)
+        .unwrap_or_default()
+}
+
+/// Parses the non-CPU lines of `/proc/stat`.
+fn parse_stat_counters(data: &str, counters: &mut KernelCounters) {
+    for line in data.lines() {
+        let mut parts = line.split_whitespace();
+        let field = match parts.next() {
+            Some("ctxt") => &mut counters.ctxt,
+            // The first value is the total, followed by the number of each interrupt.
+            Some("intr") => &mut counters.intr,
+            Some("processes") => &mut counters.processes,
+            Some("procs_running") => &mut counters.procs_running,
+            Some("procs_blocked") => &mut counters.procs_blocked,
+            _ => continue,
+        };
+        *field = parts.next().and_then(|v| v.parse().ok()).unwrap_or(0);
+    }
+}
+
+/// Parses `/proc/vmstat`.
+///
+/// The page reclaim counters are split between kswapd, direct reclaim and khugepaged (and per
+/// zone before Linux 4.8), so they are summed.
+fn parse_vmstat_counters(data: &str, counters: &mut KernelCounters) {
+    for line in data.lines() {
+        let mut parts = line.split_whitespace();
+        let (name, value) = match (parts.next(), parts.next().and_then(|v| v.parse().ok())) {
+            (Some(name), Some(value)) => (name, value),
+            _ => continue,
+        };
+        match name {
+            "pgfault" => counters.pgfault = value,
+            "pgmajfault" => counters.pgmajfault = value,
+            "pswpin" => counters.pswpin = value,
+            "pswpout" => counters.pswpout = value,
+            "oom_kill" => counters.oom_kill = value,
+            // It counts throttling events, not pages.
+            "pgscan_direct_throttle" => {}
+            _ => {
+                if is_reclaim_counter(name, "pgscan_") {
+                    counters.pgscan += value;
+                } else if is_reclaim_counter(name, "pgsteal_") {
+                    counters.pgsteal += value;
+                }
+            }
+        }
+    }
+}
+
+fn is_reclaim_counter(name: &str, prefix: &str) -> bool {
+    // `pgscan_anon`, `pgscan_file` and the others are another split of the same pages.
+    match name.strip_prefix(prefix) {
+        Some(reclaimer) => ["kswapd", "direct", "khugepaged"]
+            .iter()
+            .any(|r| reclaimer.starts_with(r)),
+        None => false,
+    }
+}
+
+fn get_kernel_limits() -> KernelLimits {
+    let read = |path: &str| get_all_data(path, 64).ok();
+    let read_u64 = |path: &str| read(path).and_then(|v| v.trim().parse().ok());
+    let mut limits = KernelLimits {
+        max_pids: read_u64("/proc/sys/kernel/pid_max").unwrap_or(0),
+        max_threads: read_u64("/proc/sys/kernel/threads-max").unwrap_or(0),
+        conntrack_entries: read_u64("/proc/sys/net/netfilter/nf_conntrack_count"),
+        max_conntrack_entries: read_u64("/proc/sys/net/netfilter/nf_conntrack_max"),
+        ..KernelLimits::default()
+    };
+    if let Some(data) = read("/proc/sys/fs/file-nr") {
+        parse_file_nr(&data, &mut limits);
+    }
+    if let Some(data) = read("/proc/sys/fs/inode-nr") {
+        let mut values = data.split_whitespace().map(|v| v.parse().unwrap_or(0));
+        limits.inodes = values.next().unwrap_or(0);
+        limits.free_inodes = values.next().unwrap_or(0);
+    }
+    // The fourth field of `/proc/loadavg` is the number of runnable threads and the number of
+    // existing threads, like `2/71`.
+    if let Some(data) = read("/proc/loadavg") {
+        limits.pids = data
+            .split_whitespace()
+            .nth(3)
+            .and_then(|v| v.split('/').nth(1))
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0);
+    }
+    limits
+}
+
+/// Parses `/proc/sys/fs/file-nr`, which contains the number of allocated file handles, the number
+/// of allocated but unused ones (always `0` since Linux 2.6) and the maximum number of them.
+fn parse_file_nr(data: &str, limits: &mut KernelLimits) {
+    let mut values = data
+        .split_whitespace()
+        .map(|v| v.parse::<u64>().unwrap_or(0));
+    let allocated = values.next().unwrap_or(0);
+    let unused = values.next().unwrap_or(0);
+    limits.open_files = allocated.saturating_sub(unused);
+    limits.max_open_files = values.next().unwrap_or(0);
+}
+
+/// Reads the `cpu`, `memory` and `io` pressure files (suffixed with `suffix`) of `dir`. Returns
+/// `None` if none of them is available.
+fn get_pressure(dir: &Path, suffix: &str) -> Option<Pressure> {
+    let read = |resource: &str| {
+        get_all_data(dir.join(format!("{}{}", resource, suffix)), 256)
+            .ok()
+            .and_then(|data| parse_pressure(&data))
+    };
+    let pressure = Pressure {
+        cpu: read("cpu"),
+        memory: read("memory"),
+        io: read("io"),
+    };
+    if pressure.cpu.is_none() && pressure.memory.is_none() && pressure.io.is_none() {
+        None
+    } else {
+        Some(pressure)
+    }
+}
+
+/// Parses the content of a pressure file, which looks like:
+///
+/// ```text
+/// some avg10=0.00 avg60=0.12 avg300=0.05 total=8835272
+/// full avg10=0.00 avg60=0.03 avg300=0.01 total=5975694
+/// ```
+fn parse_pressure(data: &str) -> Option<ResourcePressure> {
+    let mut some = None;
+    let mut full = None;
+    for line in data.lines() {
+        let mut parts = line.split_whitespace();
+        let kind = parts.next();
+        let mut stats = PressureStats::default();
+        for part in parts {
+            let mut field = part.splitn(2, '=');
+            match (field.next(), field.next()) {
+                (Some("avg10"), Some(v)) => stats.avg10 = v.parse().ok()?,
+                (Some("avg60"), Some(v)) => stats.avg60 = v.parse().ok()?,
+                (Some("avg300"), Some(v)) => stats.avg300 = v.parse().ok()?,
+                (Some("total"), Some(v)) => stats.total = v.parse().ok()?,
+                _ => {}
+            }
+        }
+        match kind {
+            Some("some") => some = Some(stats),
+            Some("full") => full = Some(stats),
+            _ => {}
+        }
+    }
+    Some(ResourcePressure { some: some?, full })
+}
+
 #[cfg(test)]
 mod test {
     #[cfg(target_os = "android")]
     use super::get_system_info_android;
     #[cfg(not(target_os = "android"))]
     use super::get_system_info_linux;
-    use super::InfoType;
+    use super::{
+        get_pressure, parse_file_nr, parse_pressure, parse_stat_counters, parse_vmstat_counters,
+        InfoType, KernelCounters,
+    };
+    use crate::{KernelLimits, PressureStats, ResourcePressure};
+
+    #[test]
+    fn check_pid_reuse() {
+        use crate::{get_current_pid, ProcessExt, System, SystemExt};
+
+        let pid = get_current_pid().expect("failed to get current pid");
+        let mut s = System::new();
+        assert!(s.refresh_process(pid));
+
+        // We simulate that the pid was previously used by another process.
+        let p = s
+            .process_list
+            .tasks
+            .get_mut(&pid)
+            .expect("process not listed");
+        p.start_time_ticks += 1;
+        p.name = "not me".to_owned();
+        p.cmd.clear();
+
+        assert!(s.refresh_process(pid));
+        let p = s.process(pid).expect("process not listed");
+        assert_ne!(p.name(), "not me");
+        assert!(!p.cmd().is_empty());
+    }
+
+    #[test]
+    fn check_reload_process_info() {
+        use crate::{get_current_pid, ProcessExt, ProcessRefreshKind, System, SystemExt};
+
+        let pid = get_current_pid().expect("failed to get current pid");
+        let mut s = System::new();
+        assert!(s.refresh_process_specifics(pid, ProcessRefreshKind::new()));
+        assert!(s.process(pid).expect("process not listed").cmd().is_empty());
+
+        assert!(s.reload_process_info(pid, ProcessRefreshKind::new().with_cmd()));
+        let p = s.process(pid).expect("process not listed");
+        assert!(!p.cmd().is_empty());
+        assert!(p.environ().is_empty());
+
+        // The information of a process which wasn't listed is read only once.
+        let mut s = System::new();
+        assert!(s.reload_process_info(pid, ProcessRefreshKind::new().with_cmd()));
+        assert!(!s.process(pid).expect("process not listed").cmd().is_empty());
+    }
 
     #[test]
     #[cfg(target_os = "android")]
@@ -746,4 +1179,134 @@ DISTRIB_DESCRIPTION="Ubuntu 20.10"
             Some("Ubuntu".to_owned())
         );
     }
+
+    #[test]
+    fn check_parse_pressure() {
+        assert_eq!(
+            parse_pressure(
+                "some avg10=1.17 avg60=2.48 avg300=5.45 total=121231903\n\
+                 full avg10=0.00 avg60=0.03 avg300=0.05 total=5975694\n"
+            ),
+            Some(ResourcePressure {
+                some: PressureStats {
+                    avg10: 1.17,
+                    avg60: 2.48,
+                    avg300: 5.45,
+                    total: 121231903,
+                },
+                full: Some(PressureStats {
+                    avg10: 0.,
+                    avg60: 0.03,
+                    avg300: 0.05,
+                    total: 5975694,
+                }),
+            })
+        );
+        // Before Linux 5.13, there is no "full" line for the CPU.
+        assert_eq!(
+            parse_pressure("some avg10=0.00 avg60=0.00 avg300=0.00 total=42\n"),
+            Some(ResourcePressure {
+                some: PressureStats {
+                    total: 42,
+                    ..PressureStats::default()
+                },
+                full: None,
+            })
+        );
+        assert_eq!(parse_pressure(""), None);
+        assert_eq!(parse_pressure("some avg10=abc\n"), None);
+    }
+
+    #[test]
+    #[cfg(not(target_os = "android"))]
+    fn check_get_pressure() {
+        let dir = tempfile::tempdir().expect("failed to create temporary directory");
+        assert_eq!(get_pressure(dir.path(), ".pressure"), None);
+
+        // A cgroup without the io and memory controllers.
+        std::fs::write(
+            dir.path().join("cpu.pressure"),
+            "some avg10=0.00 avg60=0.00 avg300=0.00 total=42\n",
+        )
+        .expect("failed to write cpu.pressure");
+        let pressure = get_pressure(dir.path(), ".pressure").expect("no pressure");
+        assert_eq!(pressure.cpu.map(|cpu| cpu.some.total), Some(42));
+        assert_eq!(pressure.memory, None);
+        assert_eq!(pressure.io, None);
+    }
+
+    #[test]
+    fn check_parse_kernel_counters() {
+        let mut counters = KernelCounters::default();
+        parse_stat_counters(
+            "cpu  10132153 290696 3084719 46828483 16683 0 25195 0 0 0\n\
+             cpu0 1393280 32966 572056 13343292 6130 0 17875 0 0 0\n\
+             intr 1462898 19 2 0 0 0 0 0 0 1 0\n\
+             ctxt 115315\n\
+             btime 769041601\n\
+             processes 86031\n\
+             procs_running 2\n\
+             procs_blocked 1\n\
+             softirq 229245889 94 60001584 13619 5175704 2471304 0 1 2 3 4\n",
+            &mut counters,
+        );
+        assert_eq!(counters.intr, 1462898);
+        assert_eq!(counters.ctxt, 115315);
+        assert_eq!(counters.processes, 86031);
+        assert_eq!(counters.procs_running, 2);
+        assert_eq!(counters.procs_blocked, 1);
+
+        parse_vmstat_counters(
+            "nr_free_pages 1024\n\
+             pswpin 12\n\
+             pswpout 34\n\
+             pgfault 5000\n\
+             pgmajfault 40\n\
+             pgsteal_kswapd 100\n\
+             pgsteal_direct 20\n\
+             pgsteal_khugepaged 3\n\
+             pgscan_kswapd 200\n\
+             pgscan_direct 40\n\
+             pgscan_khugepaged 6\n\
+             pgscan_direct_throttle 7\n\
+             pgscan_anon 146\n\
+             pgscan_file 100\n\
+             oom_kill 2\n",
+            &mut counters,
+        );
+        assert_eq!(counters.pswpin, 12);
+        assert_eq!(counters.pswpout, 34);
+        assert_eq!(counters.pgfault, 5000);
+        assert_eq!(counters.pgmajfault, 40);
+        assert_eq!(counters.pgsteal, 123);
+        assert_eq!(counters.pgscan, 246);
+        assert_eq!(counters.oom_kill, 2);
+
+        // Before Linux 4.8, the reclaim counters are per zone.
+        let mut counters = KernelCounters::default();
+        parse_vmstat_counters(
+            "pgsteal_kswapd_dma32 10\n\
+             pgsteal_kswapd_normal 20\n\
+             pgsteal_direct_normal 5\n\
+             pgscan_kswapd_dma32 30\n\
+             pgscan_kswapd_normal 40\n\
+             pgscan_direct_normal 8\n",
+            &mut counters,
+        );
+        assert_eq!(counters.pgsteal, 35);
+        assert_eq!(counters.pgscan, 78);
+    }
+
+    #[test]
+    fn check_parse_file_nr() {
+        let mut limits = KernelLimits::default();
+        parse_file_nr("9344\t0\t9223372036854775807\n", &mut limits);
+        assert_eq!(limits.open_files, 9344);
+        assert_eq!(limits.max_open_files, 9223372036854775807);
+
+        // Linux 2.4 could have unused allocated handles.
+        parse_file_nr("1024\t100\t8192\n", &mut limits);
+        assert_eq!(limits.open_files, 924);
+        assert_eq!(limits.max_open_files, 8192);
+    }
 }
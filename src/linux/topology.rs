@@ -0,0 +1,298 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::sys::utils::get_all_data;
+use crate::{CacheType, CpuCache, CpuTopology, LogicalCpu, NumaNode};
+
+use std::fs;
+use std::path::Path;
+
+pub(crate) fn get_cpu_topology() -> Option<CpuTopology> {
+    let root = Path::new("/sys/devices/system/cpu");
+    let mut cpus = fs::read_dir(root)
+        .ok()?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let id = entry
+                .file_name()
+                .to_str()?
+                .strip_prefix("cpu")?
+                .parse()
+                .ok()?;
+            // Offline CPUs don't have a topology.
+            get_logical_cpu(id, &entry.path())
+        })
+        .collect::<Vec<_>>();
+    cpus.sort_unstable_by_key(|cpu| cpu.id);
+    let cpu_list = |name: &str| {
+        read_value(&root.join(name))
+            .map(|list| parse_cpu_list(&list))
+            .unwrap_or_default()
+    };
+    Some(CpuTopology {
+        cpus,
+        online: cpu_list("online"),
+        offline: cpu_list("offline"),
+        present: cpu_list("present"),
+        possible: cpu_list("possible"),
+    })
+}
+
+fn get_logical_cpu(id: usize, path: &Path) -> Option<LogicalCpu> {
+    let topology = path.join("topology");
+    if !topology.exists() {
+        return None;
+    }
+    let cpu_list = |name: &str| {
+        read_value(&topology.join(name))
+            .map(|list| parse_cpu_list(&list))
+            .unwrap_or_default()
+    };
+    let read_id = |name: &str| read_value(&topology.join(name)).and_then(|v| v.parse().ok());
+    // `core_siblings_list` is the deprecated name of `package_cpus_list` (Linux 5.6).
+    let mut package_cpus = cpu_list("package_cpus_list");
+    if package_cpus.is_empty() {
+        package_cpus = cpu_list("core_siblings_list");
+    }
+    Some(LogicalCpu {
+        id,
+        // It is `-1` when unknown.
+        package_id: read_id("physical_package_id"),
+        die_id: read_id("die_id"),
+        core_id: read_id("core_id"),
+        thread_siblings: cpu_list("thread_siblings_list"),
+        package_cpus,
+        numa_node: get_numa_node(path),
+        caches: get_caches(&path.join("cache")),
+    })
+}
+
+/// The NUMA node of a CPU is given by a `nodeN` link in its directory.
+fn get_numa_node(path: &Path) -> Option<u32> {
+    fs::read_dir(path).ok()?.find_map(|entry| {
+        entry
+            .ok()?
+            .file_name()
+            .to_str()?
+            .strip_prefix("node")?
+            .parse()
+            .ok()
+    })
+}
+
+fn get_caches(path: &Path) -> Vec<CpuCache> {
+    let mut caches = match fs::read_dir(path) {
+        Ok(entries) => entries
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                if !path.file_name()?.to_str()?.starts_with("index") {
+                    return None;
+                }
+                let read = |name: &str| read_value(&path.join(name));
+                Some(CpuCache {
+                    level: read("level")?.parse().ok()?,
+                    kind: match read("type").as_deref() {
+                        Some("Data") => CacheType::Data,
+                        Some("Instruction") => CacheType::Instruction,
+                        Some("Unified") => CacheType::Unified,
+                        _ => CacheType::Unknown,
+                    },
+                    size: parse_cache_size(&read("size")?)?,
+                    line_size: read("coherency_line_size").and_then(|v| v.parse().ok()),
+                    ways_of_associativity: read("ways_of_associativity")
+                        .and_then(|v| v.parse().ok()),
+                    shared_cpus: read("shared_cpu_list")
+                        .map(|list| parse_cpu_list(&list))
+                        .unwrap_or_default(),
+                })
+            })
+            .collect::<Vec<_>>(),
+        Err(_) => return Vec::new(),
+    };
+    // Data before instruction caches for the same level, like in the `index*` directories.
+    caches.sort_by_key(|cache| (cache.level, cache.kind != CacheType::Data));
+    caches
+}
+
+pub(crate) fn get_numa_nodes() -> Vec<NumaNode> {
+    let root = Path::new("/sys/devices/system/node");
+    let mut nodes = match fs::read_dir(root) {
+        Ok(entries) => entries
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let id = entry
+                    .file_name()
+                    .to_str()?
+                    .strip_prefix("node")?
+                    .parse()
+                    .ok()?;
+                Some(get_numa_node_info(id, &entry.path()))
+            })
+            .collect::<Vec<_>>(),
+        Err(_) => return Vec::new(),
+    };
+    nodes.sort_unstable_by_key(|node| node.id);
+    nodes
+}
+
+fn get_numa_node_info(id: u32, path: &Path) -> NumaNode {
+    let mut node = NumaNode {
+        id,
+        cpus: read_value(&path.join("cpulist"))
+            .map(|list| parse_cpu_list(&list))
+            .unwrap_or_default(),
+        distances: read_value(&path.join("distance"))
+            .map(|distances| {
+                distances
+                    .split_whitespace()
+                    .filter_map(|d| d.parse().ok())
+                    .collect()
+            })
+            .unwrap_or_default(),
+        ..NumaNode::default()
+    };
+    if let Ok(data) = get_all_data(path.join("meminfo"), 4_096) {
+        parse_node_meminfo(&data, &mut node);
+    }
+    if let Ok(data) = get_all_data(path.join("numastat"), 512) {
+        parse_numastat(&data, &mut node);
+    }
+    node
+}
+
+/// Parses the `meminfo` file of a node, which looks like `/proc/meminfo` with a
+/// `Node <id>` prefix.
+fn parse_node_meminfo(data: &str, node: &mut NumaNode) {
+    for line in data.lines() {
+        // We skip the "Node" and node identifier parts.
+        let mut parts = line.split_whitespace().skip(2);
+        let field = match parts.next() {
+            Some("MemTotal:") => &mut node.total_memory,
+            Some("MemFree:") => &mut node.free_memory,
+            Some("MemUsed:") => &mut node.used_memory,
+            _ => continue,
+        };
+        *field = parts.next().and_then(|v| v.parse().ok()).unwrap_or(0);
+    }
+}
+
+fn parse_numastat(data: &str, node: &mut NumaNode) {
+    for line in data.lines() {
+        let mut parts = line.split_whitespace();
+        let field = match parts.next() {
+            Some("numa_hit") => &mut node.numa_hit,
+            Some("numa_miss") => &mut node.numa_miss,
+            Some("numa_foreign") => &mut node.numa_foreign,
+            Some("interleave_hit") => &mut node.interleave_hit,
+            Some("local_node") => &mut node.local_node,
+            Some("other_node") => &mut node.other_node,
+            _ => continue,
+        };
+        *field = parts.next().and_then(|v| v.parse().ok()).unwrap_or(0);
+    }
+}
+
+fn read_value(path: &Path) -> Option<String> {
+    get_all_data(path, 64).ok().map(|v| v.trim().to_owned())
+}
+
+/// Parses a list of CPUs (or NUMA nodes) like `0-3,8,10-11`.
+pub(crate) fn parse_cpu_list(list: &str) -> Vec<usize> {
+    let mut cpus = Vec::new();
+    for range in list.trim().split(',').filter(|range| !range.is_empty()) {
+        let mut bounds = range.splitn(2, '-');
+        let start = bounds.next().and_then(|v| v.trim().parse::<usize>().ok());
+        let end = match bounds.next() {
+            Some(end) => end.trim().parse().ok(),
+            None => start,
+        };
+        if let (Some(start), Some(end)) = (start, end) {
+            cpus.extend(start..=end);
+        }
+    }
+    cpus.sort_unstable();
+    cpus.dedup();
+    cpus
+}
+
+/// Parses a cache size like `48K` into bytes.
+fn parse_cache_size(size: &str) -> Option<u64> {
+    let size = size.trim();
+    let (value, unit) = match size.find(|c: char| !c.is_ascii_digit()) {
+        Some(pos) => size.split_at(pos),
+        None => (size, ""),
+    };
+    let multiplier = match unit {
+        "" => 1,
+        "K" => 1 << 10,
+        "M" => 1 << 20,
+        "G" => 1 << 30,
+        _ => return None,
+    };
+    value.parse::<u64>().ok()?.checked_mul(multiplier)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_cache_size, parse_cpu_list, parse_node_meminfo, parse_numastat};
+    use crate::NumaNode;
+
+    #[test]
+    fn check_parse_cpu_list() {
+        assert_eq!(parse_cpu_list("0\n"), vec![0]);
+        assert_eq!(parse_cpu_list("0-3,8,10-11\n"), vec![0, 1, 2, 3, 8, 10, 11]);
+        assert_eq!(parse_cpu_list("4,0-1"), vec![0, 1, 4]);
+        // No offline CPUs.
+        assert_eq!(parse_cpu_list("\n"), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn check_parse_cache_size() {
+        assert_eq!(parse_cache_size("48K\n"), Some(48 * 1024));
+        assert_eq!(parse_cache_size("2048K"), Some(2 * 1024 * 1024));
+        assert_eq!(parse_cache_size("32M"), Some(32 * 1024 * 1024));
+        assert_eq!(parse_cache_size("512"), Some(512));
+        assert_eq!(parse_cache_size("12X"), None);
+        assert_eq!(parse_cache_size(""), None);
+    }
+
+    #[test]
+    fn check_parse_numa_node() {
+        let mut node = NumaNode::default();
+        parse_node_meminfo(
+            "Node 1 MemTotal:        5471992 kB\n\
+             Node 1 MemFree:         3223272 kB\n\
+             Node 1 MemUsed:         2248720 kB\n\
+             Node 1 SwapCached:            0 kB\n\
+             Node 1 HugePages_Total:     0\n",
+            &mut node,
+        );
+        parse_numastat(
+            "numa_hit 45859356\n\
+             numa_miss 12\n\
+             numa_foreign 3\n\
+             interleave_hit 997\n\
+             local_node 45859300\n\
+             other_node 68\n",
+            &mut node,
+        );
+        assert_eq!(
+            node,
+            NumaNode {
+                total_memory: 5471992,
+                free_memory: 3223272,
+                used_memory: 2248720,
+                numa_hit: 45859356,
+                numa_miss: 12,
+                numa_foreign: 3,
+                interleave_hit: 997,
+                local_node: 45859300,
+                other_node: 68,
+                ..NumaNode::default()
+            }
+        );
+    }
+}
@@ -4,8 +4,10 @@
 // Copyright (c) 2021 Guillaume Gomez
 //
 
+use libc::{c_int, c_void};
 use std::fs::File;
 use std::io::{self, Read, Seek, SeekFrom};
+use std::mem;
 use std::path::Path;
 
 pub(crate) fn get_all_data_from_file(file: &mut File, size: usize) -> io::Result<String> {
@@ -19,3 +21,89 @@ pub(crate) fn get_all_data<P: AsRef<Path>>(file_path: P, size: usize) -> io::Res
     let mut file = File::open(file_path.as_ref())?;
     get_all_data_from_file(&mut file, size)
 }
+
+pub(crate) fn read_u16(buf: &[u8], offset: usize) -> Option<u16> {
+    let b = buf.get(offset..offset.checked_add(2)?)?;
+    Some(u16::from_ne_bytes([b[0], b[1]]))
+}
+
+pub(crate) fn read_u32(buf: &[u8], offset: usize) -> Option<u32> {
+    let b = buf.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_ne_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+pub(crate) fn read_u64(buf: &[u8], offset: usize) -> Option<u64> {
+    let b = buf.get(offset..offset.checked_add(8)?)?;
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(b);
+    Some(u64::from_ne_bytes(bytes))
+}
+
+/// A netlink socket, closed when dropped.
+#[derive(Debug)]
+pub(crate) struct NetlinkSocket(c_int);
+
+impl NetlinkSocket {
+    /// Opens a netlink socket for `protocol` and subscribes it to the multicast `groups`.
+    pub(crate) fn open(protocol: c_int, groups: u32) -> io::Result<NetlinkSocket> {
+        unsafe {
+            let fd = libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+                protocol,
+            );
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let socket = NetlinkSocket(fd);
+
+            let mut addr: libc::sockaddr_nl = mem::zeroed();
+            addr.nl_family = libc::AF_NETLINK as _;
+            addr.nl_groups = groups;
+            if libc::bind(
+                fd,
+                &addr as *const libc::sockaddr_nl as *const libc::sockaddr,
+                mem::size_of::<libc::sockaddr_nl>() as _,
+            ) != 0
+            {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(socket)
+        }
+    }
+
+    pub(crate) fn fd(&self) -> c_int {
+        self.0
+    }
+
+    /// Sends `msg` to the kernel.
+    pub(crate) fn send(&self, msg: &[u8]) -> io::Result<()> {
+        if unsafe { libc::send(self.0, msg.as_ptr() as *const c_void, msg.len(), 0) } < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(crate) fn recv(&self, buf: &mut [u8], flags: c_int) -> io::Result<usize> {
+        loop {
+            let ret =
+                unsafe { libc::recv(self.0, buf.as_mut_ptr() as *mut c_void, buf.len(), flags) };
+            if ret >= 0 {
+                return Ok(ret as usize);
+            }
+            let err = io::Error::last_os_error();
+            if err.kind() != io::ErrorKind::Interrupted {
+                return Err(err);
+            }
+        }
+    }
+}
+
+impl Drop for NetlinkSocket {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.0);
+        }
+    }
+}
@@ -0,0 +1,602 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::sys::system::System;
+use crate::sys::utils::{read_u16, read_u32, NetlinkSocket};
+use crate::{Pid, ProcessDiff, ProcessExt, ProcessRefreshKind, SystemExt, Uid};
+
+use libc::c_int;
+use std::io;
+use std::mem;
+use std::time::{Duration, Instant};
+
+const CN_IDX_PROC: u32 = 1;
+const CN_VAL_PROC: u32 = 1;
+const PROC_CN_MCAST_LISTEN: u32 = 1;
+
+const PROC_EVENT_NONE: u32 = 0x0000_0000;
+const PROC_EVENT_FORK: u32 = 0x0000_0001;
+const PROC_EVENT_EXEC: u32 = 0x0000_0002;
+const PROC_EVENT_UID: u32 = 0x0000_0004;
+const PROC_EVENT_EXIT: u32 = 0x8000_0000;
+
+// Sizes of `struct nlmsghdr`, `struct cn_msg` and of the header of `struct proc_event` (`what`,
+// `cpu` and `timestamp_ns`).
+const NLMSG_HDR_LEN: usize = 16;
+const CN_MSG_LEN: usize = 20;
+const PROC_EVENT_HDR_LEN: usize = 16;
+
+// How long we wait for the kernel to acknowledge our subscription.
+const ACK_TIMEOUT: Duration = Duration::from_millis(500);
+// How often `/proc` is scanned by default when the proc connector isn't available.
+const DEFAULT_POLLING_INTERVAL: Duration = Duration::from_millis(500);
+
+// Scanning `/proc` is costly so by default we only retrieve what is needed to detect the `exec`
+// calls.
+fn default_refresh_kind() -> ProcessRefreshKind {
+    ProcessRefreshKind::new().with_cmd()
+}
+
+/// An event reported by [`ProcessWatcher`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProcessEvent {
+    /// A new process was created.
+    Fork {
+        /// Pid of the new process.
+        pid: Pid,
+        /// Pid of the process which created it.
+        ppid: Option<Pid>,
+    },
+    /// A process replaced its program (with `execve`).
+    Exec {
+        /// Pid of the process.
+        pid: Pid,
+    },
+    /// A process ended.
+    Exit {
+        /// Pid of the process.
+        pid: Pid,
+        /// Pid of its parent, if known.
+        ppid: Option<Pid>,
+        /// Exit code of the process if it exited normally and if it is known.
+        exit_code: Option<i32>,
+        /// Number of the signal which killed the process, if any.
+        signal: Option<i32>,
+    },
+    /// The real or effective user ID of a process changed.
+    Uid {
+        /// Pid of the process.
+        pid: Pid,
+        /// New real user ID.
+        ruid: Uid,
+        /// New effective user ID.
+        euid: Uid,
+    },
+}
+
+/// Watches processes creation, `exec` and exit as they happen.
+///
+/// It uses the netlink proc connector, which requires the `CAP_NET_ADMIN` capability and to run in
+/// the initial user and PID namespaces. If it isn't available, it falls back to scanning `/proc`
+/// regularly (see [`ProcessWatcher::is_polling`] and [`ProcessWatcher::with_polling_interval`]),
+/// in which case processes which live shorter than the polling interval are missed and exit codes
+/// are unknown.
+///
+/// The [`System`] given to [`ProcessWatcher::wait_events`] is kept in sync with the received
+/// events: new processes are added, processes which `exec` are refreshed and dead ones are
+/// removed. Its processes should have been refreshed before the first call. The processes are
+/// refreshed with the [`ProcessRefreshKind`] set with [`ProcessWatcher::with_refresh_kind`]
+/// (only the command line by default, to keep the scans cheap): what it doesn't ask for is
+/// missing from the new processes and the tasks of the refreshed processes are removed unless it
+/// asks for them. The CPU usage of the processes isn't updated when using the proc connector.
+///
+/// This is only available on Linux.
+///
+/// ```no_run
+/// use sysinfo::{ProcessWatcher, System, SystemExt};
+///
+/// let mut s = System::new_all();
+/// let mut watcher = ProcessWatcher::new();
+/// loop {
+///     for event in watcher.wait_events(&mut s, None).expect("failed to get events") {
+///         println!("{:?}", event);
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ProcessWatcher {
+    connector: Option<ProcConnector>,
+    pending: Vec<ProcessEvent>,
+    polling_interval: Duration,
+    refresh_kind: ProcessRefreshKind,
+}
+
+impl ProcessWatcher {
+    /// Creates a new watcher, using the proc connector if possible.
+    pub fn new() -> ProcessWatcher {
+        let mut pending = Vec::new();
+        let connector = ProcConnector::open(&mut pending).ok();
+        if connector.is_none() {
+            sysinfo_debug!("proc connector unavailable, falling back to polling");
+            pending.clear();
+        }
+        ProcessWatcher {
+            connector,
+            pending,
+            polling_interval: DEFAULT_POLLING_INTERVAL,
+            refresh_kind: default_refresh_kind(),
+        }
+    }
+
+    /// Creates a new watcher which always scans `/proc`, even if the proc connector is available.
+    pub fn new_polling() -> ProcessWatcher {
+        ProcessWatcher {
+            connector: None,
+            pending: Vec::new(),
+            polling_interval: DEFAULT_POLLING_INTERVAL,
+            refresh_kind: default_refresh_kind(),
+        }
+    }
+
+    /// Sets how often `/proc` is scanned when polling (500 milliseconds by default).
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use sysinfo::ProcessWatcher;
+    ///
+    /// let watcher = ProcessWatcher::new().with_polling_interval(Duration::from_secs(1));
+    /// ```
+    pub fn with_polling_interval(mut self, interval: Duration) -> ProcessWatcher {
+        self.polling_interval = interval;
+        self
+    }
+
+    /// Sets what is retrieved when refreshing the processes (only the command line by default).
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessRefreshKind, ProcessWatcher};
+    ///
+    /// let watcher = ProcessWatcher::new()
+    ///     .with_refresh_kind(ProcessRefreshKind::new().with_cmd().with_environ());
+    /// ```
+    pub fn with_refresh_kind(mut self, refresh_kind: ProcessRefreshKind) -> ProcessWatcher {
+        self.refresh_kind = refresh_kind;
+        self
+    }
+
+    /// Returns `true` if this watcher scans `/proc` instead of using the proc connector.
+    pub fn is_polling(&self) -> bool {
+        self.connector.is_none()
+    }
+
+    /// Waits until at least one event is available or until `timeout` has elapsed (or forever if
+    /// `timeout` is `None`), then returns all the available events and updates `system`
+    /// accordingly.
+    ///
+    /// An empty list is returned on timeout.
+    pub fn wait_events(
+        &mut self,
+        system: &mut System,
+        timeout: Option<Duration>,
+    ) -> io::Result<Vec<ProcessEvent>> {
+        let deadline = timeout.map(|t| Instant::now() + t);
+        let mut events = match self.connector {
+            Some(ref connector) => {
+                let mut events = mem::take(&mut self.pending);
+                // Events about threads are filtered out so we might need to wait more than once.
+                while events.is_empty() && connector.wait(deadline)? {
+                    if let Err(e) = connector.read_available(&mut events) {
+                        if e.raw_os_error() != Some(libc::ENOBUFS) {
+                            return Err(e);
+                        }
+                        // The socket buffer overflowed so some events were lost: the only way to
+                        // get back in sync is to scan `/proc`.
+                        sync_system(system, &mut events, self.refresh_kind);
+                        let diff = system.refresh_processes_diff(self.refresh_kind);
+                        events.extend(events_from_diff(system, diff));
+                        return Ok(events);
+                    }
+                }
+                events
+            }
+            None => loop {
+                let diff = system.refresh_processes_diff(self.refresh_kind);
+                let events = events_from_diff(system, diff);
+                if !events.is_empty() {
+                    return Ok(events);
+                }
+                let sleep = match deadline {
+                    Some(deadline) => {
+                        let now = Instant::now();
+                        if now >= deadline {
+                            return Ok(events);
+                        }
+                        std::cmp::min(deadline - now, self.polling_interval)
+                    }
+                    None => self.polling_interval,
+                };
+                std::thread::sleep(sleep);
+            },
+        };
+        sync_system(system, &mut events, self.refresh_kind);
+        Ok(events)
+    }
+}
+
+impl Default for ProcessWatcher {
+    fn default() -> ProcessWatcher {
+        ProcessWatcher::new()
+    }
+}
+
+fn sync_system(system: &mut System, events: &mut [ProcessEvent], refresh_kind: ProcessRefreshKind) {
+    for event in events {
+        match event {
+            ProcessEvent::Fork { pid, .. } | ProcessEvent::Exec { pid } => {
+                system.refresh_process_data(*pid, refresh_kind);
+            }
+            ProcessEvent::Uid { pid, euid, .. } => {
+                if let Some(p) = system.process_mut(*pid) {
+                    p.uid = euid.0;
+                }
+            }
+            ProcessEvent::Exit { pid, ppid, .. } => {
+                if let Some(p) = system.remove_process(*pid) {
+                    if ppid.is_none() {
+                        *ppid = p.parent();
+                    }
+                }
+            }
+        }
+    }
+}
+
+// The exit events are returned first so a reused pid is reported as exited then created.
+fn events_from_diff(system: &System, diff: ProcessDiff) -> Vec<ProcessEvent> {
+    let mut events =
+        Vec::with_capacity(diff.spawned.len() + diff.exited.len() + diff.changed.len());
+    events.extend(diff.exited.into_iter().map(|p| ProcessEvent::Exit {
+        pid: p.pid,
+        ppid: None,
+        exit_code: None,
+        signal: None,
+    }));
+    events.extend(diff.spawned.into_iter().map(|pid| ProcessEvent::Fork {
+        pid,
+        ppid: system.process(pid).and_then(|p| p.parent()),
+    }));
+    events.extend(
+        diff.changed
+            .into_iter()
+            .map(|p| ProcessEvent::Exec { pid: p.pid }),
+    );
+    events
+}
+
+#[derive(Debug)]
+struct ProcConnector(NetlinkSocket);
+
+impl ProcConnector {
+    fn open(pending: &mut Vec<ProcessEvent>) -> io::Result<ProcConnector> {
+        let connector = ProcConnector(NetlinkSocket::open(libc::NETLINK_CONNECTOR, CN_IDX_PROC)?);
+        connector.0.send(&listen_message())?;
+        connector.wait_ack(pending)?;
+        Ok(connector)
+    }
+
+    // The kernel silently ignores the subscription when not called from the initial namespaces,
+    // so we need its acknowledgement to know whether we'll get events or not.
+    fn wait_ack(&self, pending: &mut Vec<ProcessEvent>) -> io::Result<()> {
+        let deadline = Some(Instant::now() + ACK_TIMEOUT);
+        let mut buf = [0u8; 4096];
+        while self.wait(deadline)? {
+            let len = self.0.recv(&mut buf, 0)?;
+            if let Some(err) = parse_messages(&buf[..len], pending) {
+                return if err == 0 {
+                    Ok(())
+                } else {
+                    Err(io::Error::from_raw_os_error(err as _))
+                };
+            }
+        }
+        Err(io::Error::new(
+            io::ErrorKind::TimedOut,
+            "proc connector subscription wasn't acknowledged",
+        ))
+    }
+
+    // Returns `false` if `deadline` was reached without any message being available.
+    fn wait(&self, deadline: Option<Instant>) -> io::Result<bool> {
+        let mut fds = libc::pollfd {
+            fd: self.0.fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        loop {
+            let timeout = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    let mut ms = remaining.as_millis();
+                    if remaining.subsec_nanos() % 1_000_000 != 0 {
+                        ms += 1;
+                    }
+                    std::cmp::min(ms, c_int::MAX as u128) as c_int
+                }
+                None => -1,
+            };
+            match unsafe { libc::poll(&mut fds, 1, timeout) } {
+                0 => return Ok(false),
+                x if x > 0 => return Ok(true),
+                _ => {
+                    let err = io::Error::last_os_error();
+                    if err.kind() != io::ErrorKind::Interrupted {
+                        return Err(err);
+                    }
+                }
+            }
+        }
+    }
+
+    // Reads all the messages currently queued on the socket.
+    fn read_available(&self, events: &mut Vec<ProcessEvent>) -> io::Result<()> {
+        let mut buf = [0u8; 4096];
+        loop {
+            match self.0.recv(&mut buf, libc::MSG_DONTWAIT) {
+                Ok(len) => {
+                    parse_messages(&buf[..len], events);
+                }
+                Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+fn listen_message() -> Vec<u8> {
+    let len = NLMSG_HDR_LEN + CN_MSG_LEN + mem::size_of::<u32>();
+    let mut msg = Vec::with_capacity(len);
+    // `struct nlmsghdr`
+    msg.extend_from_slice(&(len as u32).to_ne_bytes());
+    msg.extend_from_slice(&(libc::NLMSG_DONE as u16).to_ne_bytes());
+    msg.extend_from_slice(&0u16.to_ne_bytes());
+    msg.extend_from_slice(&0u32.to_ne_bytes());
+    msg.extend_from_slice(&std::process::id().to_ne_bytes());
+    // `struct cn_msg`
+    msg.extend_from_slice(&CN_IDX_PROC.to_ne_bytes());
+    msg.extend_from_slice(&CN_VAL_PROC.to_ne_bytes());
+    msg.extend_from_slice(&0u32.to_ne_bytes());
+    msg.extend_from_slice(&0u32.to_ne_bytes());
+    msg.extend_from_slice(&(mem::size_of::<u32>() as u16).to_ne_bytes());
+    msg.extend_from_slice(&0u16.to_ne_bytes());
+    // `enum proc_cn_mcast_op`
+    msg.extend_from_slice(&PROC_CN_MCAST_LISTEN.to_ne_bytes());
+    msg
+}
+
+fn read_pid(buf: &[u8], offset: usize) -> Option<Pid> {
+    read_u32(buf, offset).map(|p| p as Pid)
+}
+
+/// Parses the netlink messages contained in `buf` and appends the process events to `events`.
+///
+/// Returns the error code of the subscription acknowledgement if there is one.
+fn parse_messages(mut buf: &[u8], events: &mut Vec<ProcessEvent>) -> Option<u32> {
+    let mut ack = None;
+    while let Some(len) = read_u32(buf, 0) {
+        let len = len as usize;
+        if len < NLMSG_HDR_LEN || len > buf.len() {
+            break;
+        }
+        let msg = &buf[..len];
+        if read_u16(msg, 4) == Some(libc::NLMSG_DONE as u16)
+            && read_u32(msg, NLMSG_HDR_LEN) == Some(CN_IDX_PROC)
+            && read_u32(msg, NLMSG_HDR_LEN + 4) == Some(CN_VAL_PROC)
+        {
+            let data = &msg[std::cmp::min(NLMSG_HDR_LEN + CN_MSG_LEN, len)..];
+            match read_u32(data, 0) {
+                Some(PROC_EVENT_NONE) => {
+                    if let Some(err) = read_u32(data, PROC_EVENT_HDR_LEN) {
+                        ack = Some(err);
+                    }
+                }
+                Some(_) => {
+                    if let Some(event) = parse_proc_event(data) {
+                        events.push(event);
+                    }
+                }
+                None => {}
+            }
+        }
+        // Messages are aligned on 4 bytes.
+        let next = (len + 3) & !3;
+        if next >= buf.len() {
+            break;
+        }
+        buf = &buf[next..];
+    }
+    ack
+}
+
+/// Parses a `struct proc_event`. Events about threads are ignored.
+fn parse_proc_event(data: &[u8]) -> Option<ProcessEvent> {
+    let what = read_u32(data, 0)?;
+    let ev = data.get(PROC_EVENT_HDR_LEN..)?;
+    match what {
+        PROC_EVENT_FORK => {
+            let parent_tgid = read_pid(ev, 4)?;
+            let child_pid = read_pid(ev, 8)?;
+            let child_tgid = read_pid(ev, 12)?;
+            if child_pid != child_tgid {
+                return None;
+            }
+            Some(ProcessEvent::Fork {
+                pid: child_tgid,
+                ppid: Some(parent_tgid),
+            })
+        }
+        PROC_EVENT_EXEC => Some(ProcessEvent::Exec {
+            pid: read_pid(ev, 4)?,
+        }),
+        PROC_EVENT_UID => {
+            let pid = read_pid(ev, 0)?;
+            let tgid = read_pid(ev, 4)?;
+            if pid != tgid {
+                return None;
+            }
+            Some(ProcessEvent::Uid {
+                pid: tgid,
+                ruid: Uid(read_u32(ev, 8)? as _),
+                euid: Uid(read_u32(ev, 12)? as _),
+            })
+        }
+        PROC_EVENT_EXIT => {
+            let pid = read_pid(ev, 0)?;
+            let tgid = read_pid(ev, 4)?;
+            if pid != tgid {
+                return None;
+            }
+            // Same encoding as the status returned by `waitpid`.
+            let status = read_u32(ev, 8)?;
+            let (exit_code, signal) = if status & 0x7f == 0 {
+                (Some(((status >> 8) & 0xff) as i32), None)
+            } else {
+                (None, Some((status & 0x7f) as i32))
+            };
+            // The parent fields were only added in Linux 4.18.
+            let ppid = read_pid(ev, 20).filter(|&p| p != 0);
+            Some(ProcessEvent::Exit {
+                pid: tgid,
+                ppid,
+                exit_code,
+                signal,
+            })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn message(what: u32, fields: &[u32]) -> Vec<u8> {
+        let len = NLMSG_HDR_LEN + CN_MSG_LEN + PROC_EVENT_HDR_LEN + fields.len() * 4;
+        let mut msg = Vec::new();
+        msg.extend_from_slice(&(len as u32).to_ne_bytes());
+        msg.extend_from_slice(&(libc::NLMSG_DONE as u16).to_ne_bytes());
+        msg.extend_from_slice(&[0; 10]);
+        msg.extend_from_slice(&CN_IDX_PROC.to_ne_bytes());
+        msg.extend_from_slice(&CN_VAL_PROC.to_ne_bytes());
+        msg.extend_from_slice(&[0; 8]);
+        msg.extend_from_slice(&((PROC_EVENT_HDR_LEN + fields.len() * 4) as u16).to_ne_bytes());
+        msg.extend_from_slice(&[0; 2]);
+        msg.extend_from_slice(&what.to_ne_bytes());
+        msg.extend_from_slice(&[0; 12]);
+        for field in fields {
+            msg.extend_from_slice(&field.to_ne_bytes());
+        }
+        assert_eq!(msg.len(), len);
+        msg
+    }
+
+    fn parse(buf: &[u8]) -> (Vec<ProcessEvent>, Option<u32>) {
+        let mut events = Vec::new();
+        let ack = parse_messages(buf, &mut events);
+        (events, ack)
+    }
+
+    #[test]
+    fn check_listen_message() {
+        let msg = listen_message();
+        assert_eq!(msg.len(), 40);
+        assert_eq!(read_u32(&msg, 0), Some(40));
+        assert_eq!(read_u32(&msg, 16), Some(CN_IDX_PROC));
+        assert_eq!(read_u16(&msg, 32), Some(4));
+        assert_eq!(read_u32(&msg, 36), Some(PROC_CN_MCAST_LISTEN));
+    }
+
+    #[test]
+    fn check_parse_fork_and_exec() {
+        let mut buf = message(PROC_EVENT_FORK, &[10, 10, 42, 42]);
+        buf.extend(message(PROC_EVENT_EXEC, &[42, 42]));
+        // A new thread isn't a new process.
+        buf.extend(message(PROC_EVENT_FORK, &[42, 42, 43, 42]));
+        assert_eq!(
+            parse(&buf),
+            (
+                vec![
+                    ProcessEvent::Fork {
+                        pid: 42,
+                        ppid: Some(10)
+                    },
+                    ProcessEvent::Exec { pid: 42 },
+                ],
+                None
+            ),
+        );
+    }
+
+    #[test]
+    fn check_parse_exit() {
+        let mut buf = message(PROC_EVENT_EXIT, &[42, 42, 3 << 8, 17, 10, 10]);
+        buf.extend(message(PROC_EVENT_EXIT, &[43, 43, 9, 17, 10, 10]));
+        // Without the parent fields (before Linux 4.18).
+        buf.extend(message(PROC_EVENT_EXIT, &[44, 44, 0, 17]));
+        // A thread exiting isn't a process exiting.
+        buf.extend(message(PROC_EVENT_EXIT, &[45, 44, 0, 17, 10, 10]));
+        assert_eq!(
+            parse(&buf).0,
+            vec![
+                ProcessEvent::Exit {
+                    pid: 42,
+                    ppid: Some(10),
+                    exit_code: Some(3),
+                    signal: None,
+                },
+                ProcessEvent::Exit {
+                    pid: 43,
+                    ppid: Some(10),
+                    exit_code: None,
+                    signal: Some(9),
+                },
+                ProcessEvent::Exit {
+                    pid: 44,
+                    ppid: None,
+                    exit_code: Some(0),
+                    signal: None,
+                },
+            ],
+        );
+    }
+
+    #[test]
+    fn check_parse_uid_and_ack() {
+        let mut buf = message(PROC_EVENT_UID, &[42, 42, 1000, 0]);
+        buf.extend(message(PROC_EVENT_NONE, &[1]));
+        // Events we don't handle.
+        buf.extend(message(0x40, &[42, 42, 1000, 1000]));
+        assert_eq!(
+            parse(&buf),
+            (
+                vec![ProcessEvent::Uid {
+                    pid: 42,
+                    ruid: Uid(1000),
+                    euid: Uid(0),
+                }],
+                Some(1)
+            ),
+        );
+    }
+
+    #[test]
+    fn check_parse_truncated() {
+        let buf = message(PROC_EVENT_EXIT, &[42, 42, 0, 17, 10, 10]);
+        assert_eq!(parse(&buf[..buf.len() - 1]), (Vec::new(), None));
+        let mut buf = message(PROC_EVENT_FORK, &[10, 10]);
+        buf.extend(message(PROC_EVENT_EXEC, &[42, 42]));
+        assert_eq!(parse(&buf), (vec![ProcessEvent::Exec { pid: 42 }], None));
+    }
+}
@@ -9,13 +9,19 @@ use crate::{
     sys::{Component, Disk, Networks, Process, Processor},
 };
 use crate::{
-    DiskType, DiskUsage, LoadAvg, NetworksIter, Pid, ProcessStatus, RefreshKind, Signal, User,
+    Container, ContextSwitches, CpuInfo, CpuTime, CpuTopology, CpuVulnerability, DiskType,
+    DiskUsage, FrequencyScaling, HugePages, Interrupt, IoStats, KernelLimits, KernelStats, LoadAvg,
+    NamespaceKind, Namespaces, NetworkUsage, NetworksIter, NumaNode, PageFaults, Pid, Pressure,
+    ProcessDiff, ProcessFilter, ProcessRefreshKind, ProcessStatus, ProcessesMatching, RefreshKind,
+    SchedulingInfo, Signal, SwapDevice, SystemdUnitUsage, User,
 };
 
-use std::collections::HashMap;
-use std::ffi::OsStr;
+use std::collections::{HashMap, HashSet};
+use std::ffi::{OsStr, OsString};
 use std::fmt::Debug;
+use std::io;
 use std::path::Path;
+use std::time::{Duration, SystemTime};
 
 /// Contains all the methods of the [`Disk`][crate::Disk] struct.
 ///
@@ -145,6 +151,26 @@ pub trait ProcessExt: Debug {
     /// ```
     fn kill(&self, signal: Signal) -> bool;
 
+    /// Sends the given `signal` to the process and returns the error which occurred if it failed.
+    ///
+    /// If the signal isn't supported on this platform, an error of kind
+    /// [`io::ErrorKind::InvalidInput`] is returned.
+    ///
+    /// On Windows, [`Signal::Term`] asks the process to close and [`Signal::Kill`] forces it to
+    /// terminate, whereas [`ProcessExt::kill`] always forces it to terminate.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, Signal, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     if let Err(e) = process.try_kill(Signal::Term) {
+    ///         eprintln!("failed to terminate process: {}", e);
+    ///     }
+    /// }
+    /// ```
+    fn try_kill(&self, signal: Signal) -> io::Result<()>;
+
     /// Returns the name of the process.
     ///
     /// **⚠️ Important ⚠️**
@@ -216,6 +242,65 @@ pub trait ProcessExt: Debug {
     /// ```
     fn environ(&self) -> &[String];
 
+    /// Returns the command line without any conversion.
+    ///
+    /// Unlike [`ProcessExt::cmd`], arguments which aren't valid UTF-8 are kept as is, empty
+    /// arguments are kept and whitespace isn't trimmed.
+    ///
+    /// On platforms other than Linux and Android, it contains the same values as
+    /// [`ProcessExt::cmd`].
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     println!("{:?}", process.cmd_os());
+    /// }
+    /// ```
+    fn cmd_os(&self) -> &[OsString];
+
+    /// Returns the environment of the process without any conversion.
+    ///
+    /// Unlike [`ProcessExt::environ`], variables which aren't valid UTF-8 are kept as is.
+    ///
+    /// On platforms other than Linux and Android, it contains the same values as
+    /// [`ProcessExt::environ`].
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     println!("{:?}", process.environ_os());
+    /// }
+    /// ```
+    fn environ_os(&self) -> &[OsString];
+
+    /// Returns the environment of the process as key/value pairs.
+    ///
+    /// If a variable is present more than once, the first value is kept.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     if let Some(path) = process.environ_map().get(std::ffi::OsStr::new("PATH")) {
+    ///         println!("PATH={:?}", path);
+    ///     }
+    /// }
+    /// ```
+    fn environ_map(&self) -> HashMap<OsString, OsString> {
+        let mut map = HashMap::with_capacity(self.environ_os().len());
+        for var in self.environ_os() {
+            if let Some((key, value)) = crate::utils::split_env_var(var) {
+                map.entry(key).or_insert(value);
+            }
+        }
+        map
+    }
+
     /// Returns the current working directory.
     ///
     /// Always empty on Windows.
@@ -292,6 +377,177 @@ pub trait ProcessExt: Debug {
     /// ```
     fn status(&self) -> ProcessStatus;
 
+    /// Returns the id of the user owning the process.
+    ///
+    /// ⚠️ Returns `None` on Windows.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     println!("{:?}", process.user_id());
+    /// }
+    /// ```
+    fn user_id(&self) -> Option<Uid>;
+
+    /// Returns the identifiers of the Linux namespaces the process belongs to.
+    ///
+    /// They are only retrieved if [`ProcessRefreshKind::namespaces`] is set, which isn't the case
+    /// for [`SystemExt::refresh_processes`] nor for [`SystemExt::refresh_process`].
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns default values otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     println!("network namespace: {:?}", process.namespaces().net);
+    /// }
+    /// ```
+    fn namespaces(&self) -> Namespaces;
+
+    /// Returns the pid of the process as seen from inside its own pid namespace (the last entry
+    /// of `NSpid` in `/proc/[pid]/status`), or `None` if it isn't known.
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns `None` otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     println!("pid in its namespace: {:?}", process.namespace_pid());
+    /// }
+    /// ```
+    fn namespace_pid(&self) -> Option<Pid>;
+
+    /// Returns the container the process belongs to, detected from its cgroup path. Docker,
+    /// containerd, CRI-O and Podman containers (including rootless ones) are recognized, as well
+    /// as the Kubernetes pods.
+    ///
+    /// It is only retrieved if [`ProcessRefreshKind::cgroup`] is set.
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns `None` otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(container) = s.process(1337).and_then(|p| p.container()) {
+    ///     println!("container: {} ({:?})", container.id, container.runtime);
+    /// }
+    /// ```
+    fn container(&self) -> Option<&Container>;
+
+    /// Returns the systemd unit (service or scope) the process belongs to, detected from its
+    /// cgroup path. For a user service, it is the service itself, not `user@<uid>.service`.
+    ///
+    /// It is only retrieved if [`ProcessRefreshKind::cgroup`] is set.
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns `None` otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     println!("unit: {:?}", process.systemd_unit());
+    /// }
+    /// ```
+    fn systemd_unit(&self) -> Option<&str>;
+
+    /// Returns the innermost systemd slice the process belongs to, detected from its cgroup
+    /// path.
+    ///
+    /// It is only retrieved if [`ProcessRefreshKind::cgroup`] is set.
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns `None` otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     println!("slice: {:?}", process.systemd_slice());
+    /// }
+    /// ```
+    fn systemd_slice(&self) -> Option<&str>;
+
+    /// Returns the paths of the cgroups the process belongs to (one per hierarchy), as listed in
+    /// `/proc/[pid]/cgroup`.
+    ///
+    /// It is only retrieved if [`ProcessRefreshKind::cgroup`] is set.
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns an empty slice otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     println!("cgroups: {:?}", process.cgroups());
+    /// }
+    /// ```
+    fn cgroups(&self) -> &[String];
+
+    /// Returns the id of the session the process belongs to (the pid of its session leader).
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns `None` otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     println!("session: {:?}", process.session_id());
+    /// }
+    /// ```
+    fn session_id(&self) -> Option<Pid>;
+
+    /// Returns the id of the process group the process belongs to.
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns `None` otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     println!("process group: {:?}", process.process_group_id());
+    /// }
+    /// ```
+    fn process_group_id(&self) -> Option<Pid>;
+
+    /// Returns the path of the controlling terminal of the process (like `/dev/pts/0`), if any.
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns `None` otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     println!("tty: {:?}", process.tty());
+    /// }
+    /// ```
+    fn tty(&self) -> Option<&Path>;
+
+    /// Returns `true` if the process is the leader of its session (its pid is the session id).
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     println!("session leader: {}", process.is_session_leader());
+    /// }
+    /// ```
+    fn is_session_leader(&self) -> bool {
+        self.session_id() == Some(self.pid())
+    }
+
     /// Returns the time of process launch (in seconds).
     ///
     /// ```no_run
@@ -304,6 +560,37 @@ pub trait ProcessExt: Debug {
     /// ```
     fn start_time(&self) -> u64;
 
+    /// Returns the time of process launch with the best precision available on the platform.
+    ///
+    /// On Linux, it has the precision of a clock tick (usually 10 ms), which allows to tell apart
+    /// processes started during the same second. On other platforms, it is the same value as
+    /// [`ProcessExt::start_time`].
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     println!("Started at {:?}", process.start_time_precise());
+    /// }
+    /// ```
+    fn start_time_precise(&self) -> SystemTime;
+
+    /// Returns for how long the process has been running.
+    ///
+    /// It is computed from [`ProcessExt::start_time_precise`] and the current time, so it keeps
+    /// increasing between two refreshes, even if the process exited in the meantime.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     println!("Running for {:?}", process.run_time());
+    /// }
+    /// ```
+    fn run_time(&self) -> Duration;
+
     /// Returns the total CPU usage (in %). Notice that it might be bigger than 100 if run on a
     /// multicore machine.
     ///
@@ -323,27 +610,252 @@ pub trait ProcessExt: Debug {
     /// ```
     fn cpu_usage(&self) -> f32;
 
-    /// Returns number of bytes read and written to disk.
+    /// Returns the CPU time consumed by the process since it started, split between user and
+    /// system time, as of the last refresh.
+    ///
+    /// Unlike [`ProcessExt::cpu_usage`], it doesn't need two refreshes to be meaningful.
+    ///
+    /// ⚠️ Not implemented on iOS and returns zero durations there.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     let cpu_time = process.cpu_time();
+    ///     println!("user: {:?}, system: {:?}", cpu_time.user, cpu_time.system);
+    /// }
+    /// ```
+    fn cpu_time(&self) -> CpuTime;
+
+    /// Returns number of bytes read and written to disk.
+    ///
+    /// /!\\ On Windows, this method actually returns **ALL** I/O read and written bytes.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     let disk_usage = process.disk_usage();
+    ///     println!("read bytes   : new/total => {}/{}",
+    ///         disk_usage.read_bytes,
+    ///         disk_usage.total_read_bytes,
+    ///     );
+    ///     println!("written bytes: new/total => {}/{}",
+    ///         disk_usage.written_bytes,
+    ///         disk_usage.total_written_bytes,
+    ///     );
+    /// }
+    /// ```
+    fn disk_usage(&self) -> DiskUsage;
+
+    /// Returns the I/O counters of the process at the system call level: the number of bytes
+    /// read and written (including pipes, sockets and page cache hits), the number of system
+    /// calls and the number of cancelled written bytes.
+    ///
+    /// They are refreshed along with [`ProcessExt::disk_usage`].
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns default values otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     let io_stats = process.io_stats();
+    ///     println!("read chars   : new/total => {}/{}",
+    ///         io_stats.read_chars,
+    ///         io_stats.total_read_chars,
+    ///     );
+    ///     println!("written chars: new/total => {}/{}",
+    ///         io_stats.written_chars,
+    ///         io_stats.total_written_chars,
+    ///     );
+    /// }
+    /// ```
+    fn io_stats(&self) -> IoStats;
+
+    /// Returns number of bytes and packets received and transmitted over the network.
+    ///
+    /// It is only retrieved if [`ProcessRefreshKind::network_usage`] is set, which isn't the case
+    /// with [`SystemExt::refresh_processes`] since it is expensive to compute.
+    ///
+    /// On Linux, for a process in its own network namespace (a container for example), the
+    /// traffic of all the interfaces of this namespace except the loopback one is returned.
+    /// Otherwise, the TCP traffic of the sockets owned by the process is returned (a socket shared
+    /// between several processes is counted for each of them).
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns default values otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, ProcessRefreshKind, System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_process_specifics(1337, ProcessRefreshKind::new().with_network_usage());
+    /// if let Some(process) = s.process(1337) {
+    ///     let network_usage = process.network_usage();
+    ///     println!("received bytes   : new/total => {}/{}",
+    ///         network_usage.received_bytes,
+    ///         network_usage.total_received_bytes,
+    ///     );
+    ///     println!("transmitted bytes: new/total => {}/{}",
+    ///         network_usage.transmitted_bytes,
+    ///         network_usage.total_transmitted_bytes,
+    ///     );
+    /// }
+    /// ```
+    fn network_usage(&self) -> NetworkUsage;
+
+    /// Returns the scheduling information of the process: its nice value, its priority, its
+    /// scheduling policy and the processor it last ran on.
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns default values otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     let scheduling = process.scheduling();
+    ///     println!("nice: {}, priority: {}", scheduling.nice, scheduling.priority);
+    /// }
+    /// ```
+    fn scheduling(&self) -> SchedulingInfo;
+
+    /// Returns the list of the processors the process is allowed to run on.
+    ///
+    /// Unlike most methods, this information is retrieved when this method is called. It returns
+    /// an empty list if it couldn't be retrieved.
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns an empty list otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     println!("allowed on processors {:?}", process.cpu_affinity());
+    /// }
+    /// ```
+    fn cpu_affinity(&self) -> Vec<usize>;
+
+    /// Returns the number of voluntary and involuntary context switches of the process.
+    ///
+    /// It is only updated if [`ProcessRefreshKind::context_switches`] is set.
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns zero values otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     let switches = process.context_switches();
+    ///     println!("voluntary: new/total => {}/{}",
+    ///         switches.voluntary,
+    ///         switches.total_voluntary,
+    ///     );
+    /// }
+    /// ```
+    fn context_switches(&self) -> ContextSwitches;
+
+    /// Returns the number of minor and major page faults of the process.
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns zero values otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     let faults = process.page_faults();
+    ///     println!("major faults: new/total => {}/{}", faults.major, faults.total_major);
+    /// }
+    /// ```
+    fn page_faults(&self) -> PageFaults;
+
+    /// Sets the nice value of the process, from 19 (lowest priority) to -20 (highest priority).
+    ///
+    /// Lowering the nice value usually requires elevated privileges.
+    ///
+    /// ⚠️ Not implemented on Windows, returns an error of kind [`io::ErrorKind::Unsupported`].
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     process.set_priority(10).expect("failed to renice process");
+    /// }
+    /// ```
+    fn set_priority(&self, nice: i32) -> io::Result<()>;
+
+    /// Restricts the process to run on the given processors only.
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns an error of kind
+    /// [`io::ErrorKind::Unsupported`] otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     process.set_cpu_affinity(&[0, 1]).expect("failed to set CPU affinity");
+    /// }
+    /// ```
+    fn set_cpu_affinity(&self, processors: &[usize]) -> io::Result<()>;
+
+    /// Suspends the process (sends it `SIGSTOP`).
+    ///
+    /// ⚠️ Not implemented on Windows, returns an error of kind [`io::ErrorKind::Unsupported`].
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     process.suspend().expect("failed to suspend process");
+    /// }
+    /// ```
+    fn suspend(&self) -> io::Result<()>;
+
+    /// Resumes the process after it was suspended (sends it `SIGCONT`).
+    ///
+    /// ⚠️ Not implemented on Windows, returns an error of kind [`io::ErrorKind::Unsupported`].
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.process(1337) {
+    ///     process.resume().expect("failed to resume process");
+    /// }
+    /// ```
+    fn resume(&self) -> io::Result<()>;
+
+    /// Waits for the process to exit, for at most `timeout` if provided. Returns `true` if the
+    /// process exited and `false` if the timeout expired first.
     ///
-    /// /!\\ On Windows, this method actually returns **ALL** I/O read and written bytes.
+    /// The process doesn't need to be a child of the current process. On Linux, it uses a pidfd
+    /// (when available) so that a reused pid cannot be mistaken for this process. On other
+    /// platforms, it regularly checks if the process still exists.
+    ///
+    /// ⚠️ Not implemented on Windows, returns an error of kind [`io::ErrorKind::Unsupported`].
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessExt, System, SystemExt};
+    /// use std::time::Duration;
+    /// use sysinfo::{ProcessExt, Signal, System, SystemExt};
     ///
     /// let s = System::new();
     /// if let Some(process) = s.process(1337) {
-    ///     let disk_usage = process.disk_usage();
-    ///     println!("read bytes   : new/total => {}/{}",
-    ///         disk_usage.read_bytes,
-    ///         disk_usage.total_read_bytes,
-    ///     );
-    ///     println!("written bytes: new/total => {}/{}",
-    ///         disk_usage.written_bytes,
-    ///         disk_usage.total_written_bytes,
-    ///     );
+    ///     process.try_kill(Signal::Term).expect("failed to terminate process");
+    ///     if !process.wait_for_exit(Some(Duration::from_secs(5))).unwrap_or(false) {
+    ///         process.try_kill(Signal::Kill).expect("failed to kill process");
+    ///     }
     /// }
     /// ```
-    fn disk_usage(&self) -> DiskUsage;
+    fn wait_for_exit(&self, timeout: Option<Duration>) -> io::Result<bool>;
 }
 
 /// Contains all the methods of the [`Processor`][crate::Processor] struct.
@@ -399,7 +911,7 @@ pub trait ProcessorExt: Debug {
     /// ```
     fn brand(&self) -> &str;
 
-    /// Returns the processor's frequency.
+    /// Returns the processor's frequency (in MHz), or 0 if it is unknown.
     ///
     /// ```no_run
     /// use sysinfo::{ProcessorExt, System, SystemExt};
@@ -410,6 +922,29 @@ pub trait ProcessorExt: Debug {
     /// }
     /// ```
     fn frequency(&self) -> u64;
+
+    /// Returns the frequency scaling information of the processor: frequency limits, governor,
+    /// energy performance preference and time spent at each frequency. It returns `None` if
+    /// frequency scaling isn't available (in most virtual machines for example) or for
+    /// [`SystemExt::global_processor_info`].
+    ///
+    /// **Important**: this information is computed every time this function is called. It reads
+    /// about fifteen files for each processor, so avoid calling it more often than needed.
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns `None` otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessorExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for processor in s.processors() {
+    ///     let governor = processor.frequency_scaling().and_then(|scaling| scaling.governor);
+    ///     if governor.as_deref() != Some("performance") {
+    ///         println!("{} isn't using the performance governor", processor.name());
+    ///     }
+    /// }
+    /// ```
+    fn frequency_scaling(&self) -> Option<FrequencyScaling>;
 }
 
 /// Contains all the methods of the [`System`][crate::System] type.
@@ -524,6 +1059,12 @@ pub trait SystemExt: Sized + Debug + Default {
         if refreshes.users_list() {
             self.refresh_users_list();
         }
+        if refreshes.kernel_stats() {
+            self.refresh_kernel_stats();
+        }
+        if refreshes.interrupts() {
+            self.refresh_interrupts();
+        }
     }
 
     /// Refreshes system information (RAM, swap, CPU usage and components' temperature).
@@ -593,24 +1134,104 @@ pub trait SystemExt: Sized + Debug + Default {
 
     /// Gets all processes and updates their information.
     ///
+    /// It does the same as [`SystemExt::refresh_processes_specifics`]`(`[`ProcessRefreshKind::everything`]`()`[`.without_network_usage()`][ProcessRefreshKind::without_network_usage][`.without_namespaces()`][ProcessRefreshKind::without_namespaces]`)`.
+    ///
     /// ```no_run
     /// use sysinfo::{System, SystemExt};
     ///
     /// let mut s = System::new_all();
     /// s.refresh_processes();
     /// ```
-    fn refresh_processes(&mut self);
+    fn refresh_processes(&mut self) {
+        self.refresh_processes_specifics(
+            ProcessRefreshKind::everything()
+                .without_network_usage()
+                .without_namespaces(),
+        );
+    }
+
+    /// Gets all processes and updates the specified information.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessRefreshKind, System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.refresh_processes_specifics(ProcessRefreshKind::new());
+    /// ```
+    fn refresh_processes_specifics(&mut self, refresh_kind: ProcessRefreshKind);
+
+    /// Same as [`SystemExt::refresh_processes_specifics`] but also returns which processes
+    /// appeared, exited or changed since the previous refresh.
+    ///
+    /// A process is considered as changed when its name, its executable or its command line
+    /// changed, which usually means it executed another program. On Linux, it is detected when
+    /// the name of the process changes and its executable and its command line (depending on
+    /// `refresh_kind`) are then retrieved again.
+    ///
+    /// This is more expensive than [`SystemExt::refresh_processes_specifics`] since the
+    /// information about all the processes has to be kept before refreshing them.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessRefreshKind, System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// loop {
+    ///     std::thread::sleep(std::time::Duration::from_secs(1));
+    ///     let diff = s.refresh_processes_diff(ProcessRefreshKind::everything());
+    ///     println!("{} new processes, {} exited", diff.spawned.len(), diff.exited.len());
+    /// }
+    /// ```
+    fn refresh_processes_diff(&mut self, refresh_kind: ProcessRefreshKind) -> ProcessDiff {
+        let before = ProcessDiff::snapshot(self.processes());
+        self.refresh_processes_specifics(refresh_kind);
+        ProcessDiff::compute(before, self.processes())
+    }
 
     /// Refreshes *only* the process corresponding to `pid`. Returns `false` if the process doesn't
     /// exist. If it isn't listed yet, it'll be added.
     ///
+    /// It does the same as [`SystemExt::refresh_process_specifics`]`(pid, `[`ProcessRefreshKind::everything`]`()`[`.without_network_usage()`][ProcessRefreshKind::without_network_usage][`.without_namespaces()`][ProcessRefreshKind::without_namespaces]`)`.
+    ///
     /// ```no_run
     /// use sysinfo::{System, SystemExt};
     ///
     /// let mut s = System::new_all();
     /// s.refresh_process(1337);
     /// ```
-    fn refresh_process(&mut self, pid: Pid) -> bool;
+    fn refresh_process(&mut self, pid: Pid) -> bool {
+        self.refresh_process_specifics(
+            pid,
+            ProcessRefreshKind::everything()
+                .without_network_usage()
+                .without_namespaces(),
+        )
+    }
+
+    /// Refreshes *only* the process corresponding to `pid` with the specified information.
+    /// Returns `false` if the process doesn't exist. If it isn't listed yet, it'll be added.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessRefreshKind, System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.refresh_process_specifics(1337, ProcessRefreshKind::new());
+    /// ```
+    fn refresh_process_specifics(&mut self, pid: Pid, refresh_kind: ProcessRefreshKind) -> bool;
+
+    /// Refreshes the process corresponding to `pid` and retrieves again the information which
+    /// is otherwise only retrieved when the process is first listed (its command line, its
+    /// environment, etc.), as specified by `refresh_kind`. Returns `false` if the process doesn't
+    /// exist.
+    ///
+    /// It's useful if you know the process changed its command line or its environment.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessRefreshKind, System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.reload_process_info(1337, ProcessRefreshKind::new().with_cmd().with_environ());
+    /// ```
+    fn reload_process_info(&mut self, pid: Pid, refresh_kind: ProcessRefreshKind) -> bool;
 
     /// Refreshes the listed disks' information.
     ///
@@ -646,6 +1267,30 @@ pub trait SystemExt: Sized + Debug + Default {
     /// ```
     fn refresh_users_list(&mut self);
 
+    /// Refreshes the kernel activity counters.
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, does nothing otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.refresh_kernel_stats();
+    /// ```
+    fn refresh_kernel_stats(&mut self);
+
+    /// Refreshes the interrupts and softirqs counts.
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, does nothing otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.refresh_interrupts();
+    /// ```
+    fn refresh_interrupts(&mut self);
+
     /// Refreshes networks data.
     ///
     /// ```no_run
@@ -691,7 +1336,8 @@ pub trait SystemExt: Sized + Debug + Default {
         self.networks_mut().refresh_networks_list();
     }
 
-    /// Refreshes all system, processes, disks and network interfaces information.
+    /// Refreshes all system, processes, disks, network interfaces, kernel activity and interrupts
+    /// information.
     ///
     /// Please note that it doesn't recompute disks list, components list, network interfaces
     /// list nor users list.
@@ -707,6 +1353,8 @@ pub trait SystemExt: Sized + Debug + Default {
         self.refresh_processes();
         self.refresh_disks();
         self.refresh_networks();
+        self.refresh_kernel_stats();
+        self.refresh_interrupts();
     }
 
     /// Returns the process list.
@@ -753,6 +1401,167 @@ pub trait SystemExt: Sized + Debug + Default {
         ret
     }
 
+    /// Returns the pids of the processes which are in the namespace of the given `kind` with the
+    /// identifier `id` (see [`ProcessExt::namespaces`]).
+    ///
+    /// ```no_run
+    /// use sysinfo::{NamespaceKind, ProcessExt, ProcessRefreshKind, System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_processes_specifics(ProcessRefreshKind::new().with_namespaces());
+    /// if let Some(net) = s.process(1337).and_then(|p| p.namespaces().net) {
+    ///     let pids = s.processes_in_namespace(NamespaceKind::Net, net);
+    ///     println!("processes sharing the network of 1337: {:?}", pids);
+    /// }
+    /// ```
+    fn processes_in_namespace(&self, kind: NamespaceKind, id: u64) -> Vec<Pid> {
+        let mut pids = self
+            .processes()
+            .iter()
+            .filter(|(_, p)| p.namespaces().get(kind) == Some(id))
+            .map(|(&pid, _)| pid)
+            .collect::<Vec<_>>();
+        pids.sort_unstable();
+        pids
+    }
+
+    /// Returns the resources used by the processes of each systemd unit (see
+    /// [`ProcessExt::systemd_unit`]), indexed by unit name.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(usage) = s.systemd_units_usage().get("nginx.service") {
+    ///     println!("nginx: {} processes, {}% CPU", usage.processes.len(), usage.cpu_usage);
+    /// }
+    /// ```
+    fn systemd_units_usage(&self) -> HashMap<String, SystemdUnitUsage> {
+        let mut units: HashMap<String, SystemdUnitUsage> = HashMap::new();
+        for (&pid, process) in self.processes() {
+            let unit = match process.systemd_unit() {
+                Some(unit) => unit,
+                None => continue,
+            };
+            let usage = units.entry(unit.to_owned()).or_default();
+            let disk_usage = process.disk_usage();
+            usage.processes.push(pid);
+            usage.cpu_usage += process.cpu_usage();
+            usage.memory += process.memory();
+            usage.virtual_memory += process.virtual_memory();
+            usage.disk_usage.total_written_bytes += disk_usage.total_written_bytes;
+            usage.disk_usage.written_bytes += disk_usage.written_bytes;
+            usage.disk_usage.total_read_bytes += disk_usage.total_read_bytes;
+            usage.disk_usage.read_bytes += disk_usage.read_bytes;
+        }
+        for usage in units.values_mut() {
+            usage.processes.sort_unstable();
+        }
+        units
+    }
+
+    /// Returns an iterator over the processes matching `filter`.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pattern, ProcessExt, ProcessFilter, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// let filter = ProcessFilter::new().with_exe(Pattern::glob("/usr/bin/*"));
+    /// for process in s.processes_matching(&filter) {
+    ///     println!("{} {}", process.pid(), process.name());
+    /// }
+    /// ```
+    fn processes_matching<'a>(&'a self, filter: &'a ProcessFilter) -> ProcessesMatching<'a> {
+        ProcessesMatching::new(self.processes().values(), filter)
+    }
+
+    /// Refreshes only the processes matching `filter` and returns the pids of the ones which
+    /// still match after being refreshed, similarly to `pgrep`.
+    ///
+    /// Only the processes already listed are checked, so call [`SystemExt::refresh_processes`]
+    /// first to take the newly started ones into account. Only what the conditions of `filter`
+    /// need is retrieved again (for example the command line for
+    /// [`ProcessFilter::with_cmd`]), so the rest of the information checked by predicates might
+    /// be outdated.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pattern, ProcessFilter, System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// let filter = ProcessFilter::new().with_name(Pattern::exact("nginx"));
+    /// // Later on...
+    /// let pids = s.refresh_processes_matching(&filter);
+    /// println!("nginx pids: {:?}", pids);
+    /// ```
+    fn refresh_processes_matching(&mut self, filter: &ProcessFilter) -> Vec<Pid> {
+        let pids = self
+            .processes_matching(filter)
+            .map(|p| p.pid())
+            .collect::<Vec<_>>();
+        pids.into_iter()
+            .filter(|&pid| {
+                self.refresh_process_specifics(pid, filter.refresh_kind())
+                    && matches!(self.process(pid), Some(p) if filter.is_match(p))
+            })
+            .collect()
+    }
+
+    /// Sends the given `signal` to the process with the given `pid` and to all its descendants.
+    ///
+    /// The process tree is built from the current list of processes, so you might want to refresh
+    /// the processes first. The root process is signalled first so it cannot spawn new children in
+    /// the meantime. Descendants which exited before being signalled are ignored.
+    ///
+    /// Returns the list of the processes which got signalled.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Signal, System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_processes();
+    /// match s.kill_tree(1337, Signal::Kill) {
+    ///     Ok(pids) => println!("killed {:?}", pids),
+    ///     Err(e) => eprintln!("failed to kill process tree: {}", e),
+    /// }
+    /// ```
+    fn kill_tree(&self, pid: Pid, signal: Signal) -> io::Result<Vec<Pid>> {
+        let root = match self.process(pid) {
+            Some(root) => root,
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "no process with this pid",
+                ))
+            }
+        };
+        let mut children: HashMap<Pid, Vec<Pid>> = HashMap::new();
+        for (&child, process) in self.processes() {
+            if let Some(parent) = process.parent() {
+                children.entry(parent).or_default().push(child);
+            }
+        }
+        root.try_kill(signal)?;
+        let mut signalled = vec![pid];
+        let mut visited = HashSet::new();
+        visited.insert(pid);
+        let mut to_visit = vec![pid];
+        while let Some(parent) = to_visit.pop() {
+            for &child in children.get(&parent).map(|c| c.as_slice()).unwrap_or(&[]) {
+                // Protects against loops in case a pid was reused.
+                if !visited.insert(child) {
+                    continue;
+                }
+                to_visit.push(child);
+                if let Some(process) = self.process(child) {
+                    if process.try_kill(signal).is_ok() {
+                        signalled.push(child);
+                    }
+                }
+            }
+        }
+        Ok(signalled)
+    }
+
     /// Returns "global" processors information (aka the addition of all the processors).
     ///
     /// ```no_run
@@ -789,6 +1598,60 @@ pub trait SystemExt: Sized + Debug + Default {
     /// ```
     fn physical_core_count(&self) -> Option<usize>;
 
+    /// Returns the topology of the CPUs: packages, cores, hardware threads, NUMA nodes and
+    /// caches of each logical CPU, as well as the online, offline, present and possible CPUs.
+    ///
+    /// **Important**: this information is computed every time this function is called.
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns `None` otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(topology) = s.cpu_topology() {
+    ///     let hyperthreads = topology.cpus.iter().filter(|cpu| cpu.is_smt_sibling()).count();
+    ///     println!("{} hyper-threads", hyperthreads);
+    /// }
+    /// ```
+    fn cpu_topology(&self) -> Option<CpuTopology>;
+
+    /// Returns the identification and the features of the processors: family, model, stepping,
+    /// microcode revision, feature flags...
+    ///
+    /// **Important**: this information is computed every time this function is called.
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns `None` otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(info) = s.cpu_info() {
+    ///     println!("microcode: {:?}, AES: {}", info.microcode, info.has_flag("aes"));
+    /// }
+    /// ```
+    fn cpu_info(&self) -> Option<CpuInfo>;
+
+    /// Returns the status of the processors against each hardware vulnerability known by the
+    /// kernel (like `spectre_v2` or `mds`).
+    ///
+    /// **Important**: this information is computed every time this function is called.
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns an empty map otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for (name, status) in s.cpu_vulnerabilities() {
+    ///     if status.is_vulnerable() {
+    ///         println!("{} isn't mitigated: {:?}", name, status);
+    ///     }
+    /// }
+    /// ```
+    fn cpu_vulnerabilities(&self) -> HashMap<String, CpuVulnerability>;
+
     /// Returns the RAM size in KB.
     ///
     /// ```no_run
@@ -871,6 +1734,72 @@ pub trait SystemExt: Sized + Debug + Default {
     /// ```
     fn used_swap(&self) -> u64;
 
+    /// Returns the swap areas (partitions, files and zram devices), sorted by decreasing
+    /// priority.
+    ///
+    /// **Important**: this information is computed every time this function is called.
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns an empty list otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for device in s.swap_devices() {
+    ///     println!("{:?} ({:?}): {} KB used", device.path, device.kind, device.used);
+    /// }
+    /// ```
+    fn swap_devices(&self) -> Vec<SwapDevice>;
+
+    /// Returns the huge pages pools (per page size and per NUMA node) and the transparent huge
+    /// pages settings.
+    ///
+    /// **Important**: this information is computed every time this function is called.
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns default values otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for pool in s.hugepages().pools {
+    ///     println!("{} KB: {} huge pages", pool.page_size, pool.total);
+    /// }
+    /// ```
+    fn hugepages(&self) -> HugePages;
+
+    /// Returns the memory statistics of each NUMA node, sorted by identifier.
+    ///
+    /// **Important**: this information is computed every time this function is called.
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns an empty list otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for node in s.numa_nodes() {
+    ///     println!("node{}: {} KB free", node.id, node.free_memory);
+    /// }
+    /// ```
+    fn numa_nodes(&self) -> Vec<NumaNode>;
+
+    /// Returns the usage of the system-wide kernel limits: file handles, inodes, PIDs, threads
+    /// and netfilter connection tracking entries.
+    ///
+    /// **Important**: this information is computed every time this function is called.
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns default values otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// let limits = s.kernel_limits();
+    /// println!("{}/{} PIDs used", limits.pids, limits.max_pids);
+    /// ```
+    fn kernel_limits(&self) -> KernelLimits;
+
     /// Returns the components list.
     ///
     /// ```no_run
@@ -996,6 +1925,94 @@ pub trait SystemExt: Sized + Debug + Default {
     /// ```
     fn load_average(&self) -> LoadAvg;
 
+    /// Returns the pressure stall information (PSI) of the system, or `None` if the kernel
+    /// doesn't provide it.
+    ///
+    /// **Important**: this information is computed every time this function is called.
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns `None` otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(cpu) = s.pressure().and_then(|pressure| pressure.cpu) {
+    ///     println!("CPU pressure: {}%", cpu.some.avg10);
+    /// }
+    /// ```
+    fn pressure(&self) -> Option<Pressure>;
+
+    /// Returns the pressure stall information (PSI) of the given cgroup, or `None` if it doesn't
+    /// exist or if the kernel doesn't provide it.
+    ///
+    /// `cgroup` is a path in the cgroup v2 hierarchy, as listed in `/proc/[pid]/cgroup`.
+    ///
+    /// **Important**: this information is computed every time this function is called.
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns `None` otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// let pressure = s.cgroup_pressure("/system.slice/nginx.service");
+    /// if let Some(memory) = pressure.and_then(|pressure| pressure.memory) {
+    ///     println!("nginx memory pressure: {}%", memory.some.avg10);
+    /// }
+    /// ```
+    fn cgroup_pressure(&self, cgroup: &str) -> Option<Pressure>;
+
+    /// Returns the kernel activity counters: context switches, interrupts, forks, page faults,
+    /// swapping, page reclaim and OOM kills.
+    ///
+    /// The values since the last refresh are computed between the last two calls to
+    /// [`SystemExt::refresh_kernel_stats`].
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns default values otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// let stats = s.kernel_stats();
+    /// println!("{} processes killed by the OOM killer", stats.total_oom_kills);
+    /// ```
+    fn kernel_stats(&self) -> KernelStats;
+
+    /// Returns the hardware interrupts and their counts on each processor.
+    ///
+    /// The counts since the last refresh are computed between the last two calls to
+    /// [`SystemExt::refresh_interrupts`].
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns an empty list otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for interrupt in s.interrupts() {
+    ///     println!("{}: {:?}", interrupt.name, interrupt.total_per_cpu);
+    /// }
+    /// ```
+    fn interrupts(&self) -> &[Interrupt];
+
+    /// Returns the softirqs and their counts on each processor.
+    ///
+    /// The counts since the last refresh are computed between the last two calls to
+    /// [`SystemExt::refresh_interrupts`].
+    ///
+    /// ⚠️ Only implemented on Linux and Android for now, returns an empty list otherwise.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for softirq in s.softirqs() {
+    ///     println!("{}: {:?}", softirq.name, softirq.total_per_cpu);
+    /// }
+    /// ```
+    fn softirqs(&self) -> &[Interrupt];
+
     /// Returns the system name.
     ///
     /// **Important**: this information is computed every time this function is called.
@@ -4,12 +4,18 @@
 // Copyright (c) 2015 Guillaume Gomez
 //
 
-use crate::{DiskUsage, Pid, ProcessExt, Signal};
+use crate::{
+    Container, ContextSwitches, CpuTime, DiskUsage, IoStats, Namespaces, NetworkUsage, PageFaults,
+    Pid, ProcessExt, SchedulingInfo, Signal, Uid,
+};
 
+use std::ffi::OsString;
+use std::io;
 use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// Enum describing the different status of a process.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct ProcessStatus;
 
 /// Struct containing a process' information.
@@ -28,6 +34,10 @@ impl ProcessExt for Process {
         false
     }
 
+    fn try_kill(&self, _signal: Signal) -> io::Result<()> {
+        Err(unsupported())
+    }
+
     fn name(&self) -> &str {
         ""
     }
@@ -48,6 +58,14 @@ impl ProcessExt for Process {
         &[]
     }
 
+    fn cmd_os(&self) -> &[OsString] {
+        &[]
+    }
+
+    fn environ_os(&self) -> &[OsString] {
+        &[]
+    }
+
     fn cwd(&self) -> &Path {
         &Path::new("")
     }
@@ -72,15 +90,118 @@ impl ProcessExt for Process {
         ProcessStatus
     }
 
+    fn user_id(&self) -> Option<Uid> {
+        None
+    }
+
+    fn namespaces(&self) -> Namespaces {
+        Namespaces::default()
+    }
+
+    fn namespace_pid(&self) -> Option<Pid> {
+        None
+    }
+
+    fn container(&self) -> Option<&Container> {
+        None
+    }
+
+    fn systemd_unit(&self) -> Option<&str> {
+        None
+    }
+
+    fn systemd_slice(&self) -> Option<&str> {
+        None
+    }
+
+    fn cgroups(&self) -> &[String] {
+        &[]
+    }
+
+    fn session_id(&self) -> Option<Pid> {
+        None
+    }
+
+    fn process_group_id(&self) -> Option<Pid> {
+        None
+    }
+
+    fn tty(&self) -> Option<&Path> {
+        None
+    }
+
     fn start_time(&self) -> u64 {
         0
     }
 
+    fn start_time_precise(&self) -> SystemTime {
+        UNIX_EPOCH
+    }
+
+    fn run_time(&self) -> Duration {
+        Duration::default()
+    }
+
     fn cpu_usage(&self) -> f32 {
         0.0
     }
 
+    fn cpu_time(&self) -> CpuTime {
+        CpuTime::default()
+    }
+
     fn disk_usage(&self) -> DiskUsage {
         DiskUsage::default()
     }
+
+    fn io_stats(&self) -> IoStats {
+        IoStats::default()
+    }
+
+    fn network_usage(&self) -> NetworkUsage {
+        NetworkUsage::default()
+    }
+
+    fn scheduling(&self) -> SchedulingInfo {
+        SchedulingInfo::default()
+    }
+
+    fn cpu_affinity(&self) -> Vec<usize> {
+        Vec::new()
+    }
+
+    fn context_switches(&self) -> ContextSwitches {
+        ContextSwitches::default()
+    }
+
+    fn page_faults(&self) -> PageFaults {
+        PageFaults::default()
+    }
+
+    fn set_priority(&self, _nice: i32) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    fn set_cpu_affinity(&self, _processors: &[usize]) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    fn suspend(&self) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    fn resume(&self) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    fn wait_for_exit(&self, _timeout: Option<Duration>) -> io::Result<bool> {
+        Err(unsupported())
+    }
+}
+
+fn unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "this operation is not supported on this platform",
+    )
 }
@@ -4,7 +4,7 @@
 // Copyright (c) 2015 Guillaume Gomez
 //
 
-use crate::ProcessorExt;
+use crate::{FrequencyScaling, ProcessorExt};
 
 /// Dummy struct that represents a processor.
 pub struct Processor {}
@@ -35,4 +35,8 @@ impl ProcessorExt for Processor {
     fn brand(&self) -> &str {
         ""
     }
+
+    fn frequency_scaling(&self) -> Option<FrequencyScaling> {
+        None
+    }
 }
@@ -6,7 +6,8 @@
 
 use crate::{
     sys::{component::Component, Disk, Networks, Process, Processor},
-    LoadAvg, Pid, RefreshKind, SystemExt, User,
+    CpuInfo, CpuTopology, CpuVulnerability, HugePages, Interrupt, KernelLimits, KernelStats,
+    LoadAvg, NumaNode, Pid, Pressure, ProcessRefreshKind, RefreshKind, SwapDevice, SystemExt, User,
 };
 
 use std::collections::HashMap;
@@ -35,9 +36,13 @@ impl SystemExt for System {
 
     fn refresh_components_list(&mut self) {}
 
-    fn refresh_processes(&mut self) {}
+    fn refresh_processes_specifics(&mut self, _refresh_kind: ProcessRefreshKind) {}
 
-    fn refresh_process(&mut self, _pid: Pid) -> bool {
+    fn refresh_process_specifics(&mut self, _pid: Pid, _refresh_kind: ProcessRefreshKind) -> bool {
+        false
+    }
+
+    fn reload_process_info(&mut self, _pid: Pid, _refresh_kind: ProcessRefreshKind) -> bool {
         false
     }
 
@@ -45,6 +50,10 @@ impl SystemExt for System {
 
     fn refresh_users_list(&mut self) {}
 
+    fn refresh_kernel_stats(&mut self) {}
+
+    fn refresh_interrupts(&mut self) {}
+
     // COMMON PART
     //
     // Need to be moved into a "common" file to avoid duplication.
@@ -77,6 +86,18 @@ impl SystemExt for System {
         None
     }
 
+    fn cpu_topology(&self) -> Option<CpuTopology> {
+        None
+    }
+
+    fn cpu_info(&self) -> Option<CpuInfo> {
+        None
+    }
+
+    fn cpu_vulnerabilities(&self) -> HashMap<String, CpuVulnerability> {
+        HashMap::new()
+    }
+
     fn total_memory(&self) -> u64 {
         0
     }
@@ -105,6 +126,22 @@ impl SystemExt for System {
         0
     }
 
+    fn swap_devices(&self) -> Vec<SwapDevice> {
+        Vec::new()
+    }
+
+    fn hugepages(&self) -> HugePages {
+        HugePages::default()
+    }
+
+    fn numa_nodes(&self) -> Vec<NumaNode> {
+        Vec::new()
+    }
+
+    fn kernel_limits(&self) -> KernelLimits {
+        KernelLimits::default()
+    }
+
     fn components(&self) -> &[Component] {
         &[]
     }
@@ -137,6 +174,26 @@ impl SystemExt for System {
         }
     }
 
+    fn pressure(&self) -> Option<Pressure> {
+        None
+    }
+
+    fn cgroup_pressure(&self, _cgroup: &str) -> Option<Pressure> {
+        None
+    }
+
+    fn kernel_stats(&self) -> KernelStats {
+        KernelStats::default()
+    }
+
+    fn interrupts(&self) -> &[Interrupt] {
+        &[]
+    }
+
+    fn softirqs(&self) -> &[Interrupt] {
+        &[]
+    }
+
     fn users(&self) -> &[User] {
         &[]
     }
@@ -9,6 +9,8 @@ use std::{ffi::OsStr, os::unix::ffi::OsStrExt, path::Path};
 
 use crate::Pid;
 
+use std::ffi::OsString;
+
 #[allow(clippy::useless_conversion)]
 #[cfg(any(target_os = "linux", target_os = "android"))]
 pub fn realpath(original: &Path) -> std::path::PathBuf {
@@ -51,6 +53,29 @@ pub fn to_cpath(path: &Path) -> Vec<u8> {
     cpath
 }
 
+/// Splits an environment variable of the form `KEY=value` into its key and its value.
+///
+/// The first character is never considered as a separator because on Windows, some variables
+/// names start with `=`.
+pub fn split_env_var(var: &std::ffi::OsStr) -> Option<(OsString, OsString)> {
+    cfg_if::cfg_if! {
+        if #[cfg(unix)] {
+            use std::os::unix::ffi::OsStrExt;
+
+            let bytes = var.as_bytes();
+            let pos = bytes.iter().skip(1).position(|&c| c == b'=')? + 1;
+            Some((
+                std::ffi::OsStr::from_bytes(&bytes[..pos]).to_os_string(),
+                std::ffi::OsStr::from_bytes(&bytes[pos + 1..]).to_os_string(),
+            ))
+        } else {
+            let var = var.to_string_lossy();
+            let pos = var.char_indices().skip(1).find(|&(_, c)| c == '=')?.0;
+            Some((OsString::from(&var[..pos]), OsString::from(&var[pos + 1..])))
+        }
+    }
+}
+
 /// Returns the pid for the current process.
 ///
 /// `Err` is returned in case the platform isn't supported.
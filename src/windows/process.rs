@@ -4,9 +4,14 @@
 // Copyright (c) 2018 Guillaume Gomez
 //
 
-use crate::{DiskUsage, Pid, ProcessExt, Signal};
+use crate::{
+    Container, ContextSwitches, CpuTime, DiskUsage, IoStats, Namespaces, NetworkUsage, PageFaults,
+    Pid, ProcessExt, ProcessRefreshKind, SchedulingInfo, Signal, Uid,
+};
 
+use std::ffi::OsString;
 use std::fmt::{self, Debug};
+use std::io;
 use std::mem::{size_of, zeroed, MaybeUninit};
 use std::ops::Deref;
 use std::os::windows::process::CommandExt;
@@ -14,6 +19,7 @@ use std::path::{Path, PathBuf};
 use std::process;
 use std::ptr::null_mut;
 use std::str;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use libc::{c_void, memcpy};
 
@@ -43,7 +49,7 @@ use winapi::um::winnt::{
 };
 
 /// Enum describing the different status of a process.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ProcessStatus {
     /// Currently runnable.
     Run,
@@ -95,9 +101,11 @@ unsafe impl<T: Clone> Sync for PtrWrapper<T> {}
 pub struct Process {
     name: String,
     cmd: Vec<String>,
+    cmd_os: Vec<OsString>,
     exe: PathBuf,
     pid: Pid,
     environ: Vec<String>,
+    environ_os: Vec<OsString>,
     cwd: PathBuf,
     root: PathBuf,
     pub(crate) memory: u64,
@@ -108,6 +116,7 @@ pub struct Process {
     cpu_calc_values: CPUsageCalculationValues,
     start_time: u64,
     cpu_usage: f32,
+    cpu_time: CpuTime,
     pub(crate) updated: bool,
     old_read_bytes: u64,
     old_written_bytes: u64,
@@ -240,6 +249,7 @@ impl Process {
             let mut h_mod = null_mut();
             unsafe { get_h_mod(handle, &mut h_mod) };
             let environ = unsafe { get_proc_env(handle, pid as u32, &name) };
+            let cmd = get_cmd_line(handle);
 
             let exe = unsafe { get_exe(handle, h_mod) };
             let mut root = exe.clone();
@@ -249,7 +259,9 @@ impl Process {
                 name,
                 pid,
                 parent,
-                cmd: get_cmd_line(handle),
+                cmd_os: to_os_strings(&cmd),
+                cmd,
+                environ_os: to_os_strings(&environ),
                 environ,
                 exe,
                 cwd: PathBuf::new(),
@@ -259,6 +271,7 @@ impl Process {
                 virtual_memory,
                 cpu_usage: 0.,
                 cpu_calc_values: CPUsageCalculationValues::new(),
+                cpu_time: CpuTime::default(),
                 start_time: unsafe { get_start_time(handle) },
                 updated: true,
                 old_read_bytes: 0,
@@ -273,7 +286,9 @@ impl Process {
                 pid,
                 parent,
                 cmd: Vec::new(),
+                cmd_os: Vec::new(),
                 environ: Vec::new(),
+                environ_os: Vec::new(),
                 exe: get_executable_path(pid),
                 cwd: PathBuf::new(),
                 root: PathBuf::new(),
@@ -282,6 +297,7 @@ impl Process {
                 virtual_memory,
                 cpu_usage: 0.,
                 cpu_calc_values: CPUsageCalculationValues::new(),
+                cpu_time: CpuTime::default(),
                 start_time: 0,
                 updated: true,
                 old_read_bytes: 0,
@@ -302,6 +318,7 @@ impl Process {
                 String::new()
             };
             let environ = get_proc_env(process_handler, pid as u32, &name);
+            let cmd = get_cmd_line(process_handler);
 
             let exe = get_exe(process_handler, h_mod);
             let mut root = exe.clone();
@@ -311,7 +328,9 @@ impl Process {
                 name,
                 pid,
                 parent,
-                cmd: get_cmd_line(process_handler),
+                cmd_os: to_os_strings(&cmd),
+                cmd,
+                environ_os: to_os_strings(&environ),
                 environ,
                 exe,
                 cwd: PathBuf::new(),
@@ -321,6 +340,7 @@ impl Process {
                 virtual_memory: 0,
                 cpu_usage: 0.,
                 cpu_calc_values: CPUsageCalculationValues::new(),
+                cpu_time: CpuTime::default(),
                 start_time: get_start_time(process_handler),
                 updated: true,
                 old_read_bytes: 0,
@@ -348,7 +368,9 @@ impl ProcessExt for Process {
                 pid,
                 parent,
                 cmd: Vec::new(),
+                cmd_os: Vec::new(),
                 environ: Vec::new(),
+                environ_os: Vec::new(),
                 exe: get_executable_path(pid),
                 cwd: PathBuf::new(),
                 root: PathBuf::new(),
@@ -357,6 +379,7 @@ impl ProcessExt for Process {
                 virtual_memory: 0,
                 cpu_usage: 0.,
                 cpu_calc_values: CPUsageCalculationValues::new(),
+                cpu_time: CpuTime::default(),
                 start_time: 0,
                 updated: true,
                 old_read_bytes: 0,
@@ -377,6 +400,34 @@ impl ProcessExt for Process {
         }
     }
 
+    fn try_kill(&self, signal: Signal) -> io::Result<()> {
+        let mut kill = process::Command::new("taskkill.exe");
+        kill.arg("/PID").arg(self.pid().to_string());
+        match signal {
+            // Without `/F`, the process is asked to close like with `SIGTERM`.
+            Signal::Term => {}
+            Signal::Kill => {
+                kill.arg("/F");
+            }
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "signal not supported on this platform",
+                ))
+            }
+        }
+        kill.creation_flags(CREATE_NO_WINDOW);
+        let output = kill.output()?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::Other,
+                String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+            ))
+        }
+    }
+
     fn name(&self) -> &str {
         &self.name
     }
@@ -397,6 +448,14 @@ impl ProcessExt for Process {
         &self.environ
     }
 
+    fn cmd_os(&self) -> &[OsString] {
+        &self.cmd_os
+    }
+
+    fn environ_os(&self) -> &[OsString] {
+        &self.environ_os
+    }
+
     fn cwd(&self) -> &Path {
         self.cwd.as_path()
     }
@@ -421,14 +480,68 @@ impl ProcessExt for Process {
         self.status
     }
 
+    fn user_id(&self) -> Option<Uid> {
+        None
+    }
+
+    fn namespaces(&self) -> Namespaces {
+        Namespaces::default()
+    }
+
+    fn namespace_pid(&self) -> Option<Pid> {
+        None
+    }
+
+    fn container(&self) -> Option<&Container> {
+        None
+    }
+
+    fn systemd_unit(&self) -> Option<&str> {
+        None
+    }
+
+    fn systemd_slice(&self) -> Option<&str> {
+        None
+    }
+
+    fn cgroups(&self) -> &[String] {
+        &[]
+    }
+
+    fn session_id(&self) -> Option<Pid> {
+        None
+    }
+
+    fn process_group_id(&self) -> Option<Pid> {
+        None
+    }
+
+    fn tty(&self) -> Option<&Path> {
+        None
+    }
+
     fn start_time(&self) -> u64 {
         self.start_time
     }
 
+    fn start_time_precise(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(self.start_time)
+    }
+
+    fn run_time(&self) -> Duration {
+        SystemTime::now()
+            .duration_since(self.start_time_precise())
+            .unwrap_or_default()
+    }
+
     fn cpu_usage(&self) -> f32 {
         self.cpu_usage
     }
 
+    fn cpu_time(&self) -> CpuTime {
+        self.cpu_time
+    }
+
     fn disk_usage(&self) -> DiskUsage {
         DiskUsage {
             written_bytes: self.written_bytes - self.old_written_bytes,
@@ -437,6 +550,57 @@ impl ProcessExt for Process {
             total_read_bytes: self.read_bytes,
         }
     }
+
+    fn io_stats(&self) -> IoStats {
+        IoStats::default()
+    }
+
+    fn network_usage(&self) -> NetworkUsage {
+        NetworkUsage::default()
+    }
+
+    fn scheduling(&self) -> SchedulingInfo {
+        SchedulingInfo::default()
+    }
+
+    fn cpu_affinity(&self) -> Vec<usize> {
+        Vec::new()
+    }
+
+    fn context_switches(&self) -> ContextSwitches {
+        ContextSwitches::default()
+    }
+
+    fn page_faults(&self) -> PageFaults {
+        PageFaults::default()
+    }
+
+    fn set_priority(&self, _nice: i32) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    fn set_cpu_affinity(&self, _processors: &[usize]) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    fn suspend(&self) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    fn resume(&self) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    fn wait_for_exit(&self, _timeout: Option<Duration>) -> io::Result<bool> {
+        Err(unsupported())
+    }
+}
+
+fn unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "this operation is not supported on this platform",
+    )
 }
 
 impl Drop for Process {
@@ -775,6 +939,12 @@ pub(crate) fn compute_cpu_usage(p: &mut Process, nb_processors: u64, _now: ULARG
         let global_kernel_time = *global_kernel_time.QuadPart();
         let global_user_time = *global_user_time.QuadPart();
 
+        // `FILETIME` values are expressed in 100-nanosecond intervals.
+        p.cpu_time = CpuTime {
+            user: Duration::from_nanos(user.saturating_mul(100)),
+            system: Duration::from_nanos(sys.saturating_mul(100)),
+        };
+
         let delta_global_kernel_time =
             check_sub(global_kernel_time, p.cpu_calc_values.old_system_sys_cpu);
         let delta_global_user_time =
@@ -804,6 +974,24 @@ pub fn get_handle(p: &Process) -> HANDLE {
     *p.handle
 }
 
+pub(crate) fn update_process_info(p: &mut Process, refresh_kind: ProcessRefreshKind) {
+    if p.handle.is_null() {
+        return;
+    }
+    if refresh_kind.cmd() {
+        p.cmd = get_cmd_line(*p.handle);
+        p.cmd_os = to_os_strings(&p.cmd);
+    }
+    if refresh_kind.environ() {
+        p.environ = unsafe { get_proc_env(*p.handle, p.pid as u32, &p.name) };
+        p.environ_os = to_os_strings(&p.environ);
+    }
+}
+
+fn to_os_strings(values: &[String]) -> Vec<OsString> {
+    values.iter().map(OsString::from).collect()
+}
+
 pub fn update_disk_usage(p: &mut Process) {
     let mut counters = MaybeUninit::<IO_COUNTERS>::uninit();
     let ret = unsafe { GetProcessIoCounters(*p.handle, counters.as_mut_ptr()) };
@@ -5,7 +5,7 @@
 //
 
 use crate::sys::tools::KeyHandler;
-use crate::{LoadAvg, ProcessorExt};
+use crate::{FrequencyScaling, LoadAvg, ProcessorExt};
 
 use std::collections::HashMap;
 use std::io::Error;
@@ -262,6 +262,10 @@ impl ProcessorExt for Processor {
     fn brand(&self) -> &str {
         &self.brand
     }
+
+    fn frequency_scaling(&self) -> Option<FrequencyScaling> {
+        None
+    }
 }
 
 impl Processor {
@@ -4,14 +4,18 @@
 // Copyright (c) 2018 Guillaume Gomez
 //
 
-use crate::{LoadAvg, Networks, Pid, ProcessExt, RefreshKind, SystemExt, User};
+use crate::{
+    CpuInfo, CpuTopology, CpuVulnerability, HugePages, Interrupt, KernelLimits, KernelStats,
+    LoadAvg, Networks, NumaNode, Pid, Pressure, ProcessExt, ProcessRefreshKind, RefreshKind,
+    SwapDevice, SystemExt, User,
+};
 use winapi::um::winreg::HKEY_LOCAL_MACHINE;
 
 use crate::sys::component::{self, Component};
 use crate::sys::disk::Disk;
 use crate::sys::process::{
     compute_cpu_usage, get_handle, get_system_computation_time, update_disk_usage, update_memory,
-    Process,
+    update_process_info, Process,
 };
 use crate::sys::processor::*;
 use crate::sys::tools::*;
@@ -192,9 +196,9 @@ impl SystemExt for System {
     }
 
     #[allow(clippy::map_entry)]
-    fn refresh_process(&mut self, pid: Pid) -> bool {
+    fn refresh_process_specifics(&mut self, pid: Pid, refresh_kind: ProcessRefreshKind) -> bool {
         if self.process_list.contains_key(&pid) {
-            if !refresh_existing_process(self, pid) {
+            if !refresh_existing_process(self, pid, refresh_kind) {
                 self.process_list.remove(&pid);
                 return false;
             }
@@ -202,7 +206,9 @@ impl SystemExt for System {
         } else if let Some(mut p) = Process::new_from_pid(pid) {
             let system_time = get_system_computation_time();
             compute_cpu_usage(&mut p, self.processors.len() as u64, system_time);
-            update_disk_usage(&mut p);
+            if refresh_kind.disk_usage() {
+                update_disk_usage(&mut p);
+            }
             self.process_list.insert(pid, p);
             true
         } else {
@@ -211,7 +217,7 @@ impl SystemExt for System {
     }
 
     #[allow(clippy::cast_ptr_alignment)]
-    fn refresh_processes(&mut self) {
+    fn refresh_processes_specifics(&mut self, refresh_kind: ProcessRefreshKind) {
         // Windows 10 notebook requires at least 512KiB of memory to make it in one go
         let mut buffer_size: usize = 512 * 1024;
 
@@ -274,7 +280,9 @@ impl SystemExt for System {
                             proc_.memory = (pi.WorkingSetSize as u64) / 1_000;
                             proc_.virtual_memory = (pi.VirtualSize as u64) / 1_000;
                             compute_cpu_usage(proc_, nb_processors, system_time);
-                            update_disk_usage(proc_);
+                            if refresh_kind.disk_usage() {
+                                update_disk_usage(proc_);
+                            }
                             proc_.updated = true;
                             return None;
                         }
@@ -291,7 +299,9 @@ impl SystemExt for System {
                             name,
                         );
                         compute_cpu_usage(&mut p, nb_processors, system_time);
-                        update_disk_usage(&mut p);
+                        if refresh_kind.disk_usage() {
+                            update_disk_usage(&mut p);
+                        }
                         Some(p)
                     })
                     .collect::<Vec<_>>();
@@ -318,6 +328,19 @@ impl SystemExt for System {
         }
     }
 
+    fn reload_process_info(&mut self, pid: Pid, refresh_kind: ProcessRefreshKind) -> bool {
+        if !self.refresh_process_specifics(pid, refresh_kind) {
+            return false;
+        }
+        match self.process_list.get_mut(&(pid as usize)) {
+            Some(p) => {
+                update_process_info(p, refresh_kind);
+                true
+            }
+            None => false,
+        }
+    }
+
     fn refresh_disks_list(&mut self) {
         self.disks = unsafe { get_disks() };
     }
@@ -326,6 +349,10 @@ impl SystemExt for System {
         self.users = unsafe { get_users() };
     }
 
+    fn refresh_kernel_stats(&mut self) {}
+
+    fn refresh_interrupts(&mut self) {}
+
     fn processes(&self) -> &HashMap<Pid, Process> {
         &self.process_list
     }
@@ -346,6 +373,18 @@ impl SystemExt for System {
         get_physical_core_count()
     }
 
+    fn cpu_topology(&self) -> Option<CpuTopology> {
+        None
+    }
+
+    fn cpu_info(&self) -> Option<CpuInfo> {
+        None
+    }
+
+    fn cpu_vulnerabilities(&self) -> HashMap<String, CpuVulnerability> {
+        HashMap::new()
+    }
+
     fn total_memory(&self) -> u64 {
         self.mem_total
     }
@@ -375,6 +414,22 @@ impl SystemExt for System {
         self.swap_used
     }
 
+    fn swap_devices(&self) -> Vec<SwapDevice> {
+        Vec::new()
+    }
+
+    fn hugepages(&self) -> HugePages {
+        HugePages::default()
+    }
+
+    fn numa_nodes(&self) -> Vec<NumaNode> {
+        Vec::new()
+    }
+
+    fn kernel_limits(&self) -> KernelLimits {
+        KernelLimits::default()
+    }
+
     fn components(&self) -> &[Component] {
         &self.components
     }
@@ -415,6 +470,26 @@ impl SystemExt for System {
         get_load_average()
     }
 
+    fn pressure(&self) -> Option<Pressure> {
+        None
+    }
+
+    fn cgroup_pressure(&self, _cgroup: &str) -> Option<Pressure> {
+        None
+    }
+
+    fn kernel_stats(&self) -> KernelStats {
+        KernelStats::default()
+    }
+
+    fn interrupts(&self) -> &[Interrupt] {
+        &[]
+    }
+
+    fn softirqs(&self) -> &[Interrupt] {
+        &[]
+    }
+
     fn name(&self) -> Option<String> {
         Some("Windows".to_owned())
     }
@@ -472,13 +547,15 @@ fn is_proc_running(handle: HANDLE) -> bool {
     !(ret == FALSE || exit_code != STILL_ACTIVE)
 }
 
-fn refresh_existing_process(s: &mut System, pid: Pid) -> bool {
+fn refresh_existing_process(s: &mut System, pid: Pid, refresh_kind: ProcessRefreshKind) -> bool {
     if let Some(ref mut entry) = s.process_list.get_mut(&(pid as usize)) {
         if !is_proc_running(get_handle(entry)) {
             return false;
         }
         update_memory(entry);
-        update_disk_usage(entry);
+        if refresh_kind.disk_usage() {
+            update_disk_usage(entry);
+        }
         compute_cpu_usage(
             entry,
             s.processors.len() as u64,
@@ -0,0 +1,34 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+#[test]
+fn test_hugepages() {
+    use sysinfo::{HugePages, SystemExt};
+
+    let s = sysinfo::System::new();
+    let hugepages = s.hugepages();
+    if !cfg!(any(target_os = "linux", target_os = "android")) {
+        assert_eq!(hugepages, HugePages::default());
+        return;
+    }
+    for (i, pool) in hugepages.pools.iter().enumerate() {
+        assert!(pool.page_size > 0);
+        assert!(pool.free <= pool.total);
+        if !pool.nodes.is_empty() {
+            assert_eq!(
+                pool.nodes.iter().map(|node| node.total).sum::<u64>(),
+                pool.total
+            );
+        }
+        if i > 0 {
+            assert!(hugepages.pools[i - 1].page_size < pool.page_size);
+        }
+    }
+    if let Some(transparent) = hugepages.transparent {
+        assert!(!transparent.enabled.is_empty());
+        assert!(!transparent.defrag.is_empty());
+    }
+}
@@ -0,0 +1,35 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+#[test]
+fn test_interrupts() {
+    use sysinfo::{RefreshKind, SystemExt};
+
+    let mut s = sysinfo::System::new_with_specifics(RefreshKind::new().with_interrupts());
+    if !cfg!(any(target_os = "linux", target_os = "android")) {
+        assert!(s.interrupts().is_empty());
+        assert!(s.softirqs().is_empty());
+        return;
+    }
+    assert!(!s.interrupts().is_empty());
+    assert!(s.softirqs().iter().any(|softirq| softirq.name == "NET_RX"));
+
+    let before = s.softirqs().to_vec();
+    s.refresh_interrupts();
+    for softirq in s.softirqs() {
+        assert!(softirq.description.is_empty());
+        assert_eq!(softirq.total_per_cpu.len(), softirq.per_cpu.len());
+        assert_eq!(softirq.cpus.len(), softirq.per_cpu.len());
+        let old = before.iter().find(|old| old.name == softirq.name).unwrap();
+        for (i, total) in softirq.total_per_cpu.iter().enumerate() {
+            assert_eq!(*total, old.total_per_cpu[i] + softirq.per_cpu[i]);
+        }
+    }
+    for interrupt in s.interrupts() {
+        assert!(!interrupt.name.is_empty());
+        assert!(!interrupt.total_per_cpu.is_empty());
+    }
+}
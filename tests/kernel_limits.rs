@@ -0,0 +1,25 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+#[test]
+fn test_kernel_limits() {
+    use sysinfo::{KernelLimits, SystemExt};
+
+    let s = sysinfo::System::new();
+    let limits = s.kernel_limits();
+    if !cfg!(any(target_os = "linux", target_os = "android")) {
+        assert_eq!(limits, KernelLimits::default());
+        return;
+    }
+    assert!(limits.open_files > 0);
+    assert!(limits.open_files <= limits.max_open_files);
+    assert!(limits.pids > 0);
+    assert!(limits.pids <= limits.max_pids);
+    assert!(limits.max_threads > 0);
+    if let (Some(entries), Some(max)) = (limits.conntrack_entries, limits.max_conntrack_entries) {
+        assert!(entries <= max);
+    }
+}
@@ -0,0 +1,33 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+#[test]
+fn test_kernel_stats() {
+    use sysinfo::{RefreshKind, SystemExt};
+
+    let mut s = sysinfo::System::new_with_specifics(RefreshKind::new().with_kernel_stats());
+    if !cfg!(any(target_os = "linux", target_os = "android")) {
+        assert_eq!(s.kernel_stats(), Default::default());
+        return;
+    }
+    let first = s.kernel_stats();
+    assert!(first.total_context_switches > 0);
+    assert!(first.total_interrupts > 0);
+    assert!(first.total_forks > 0);
+    assert!(first.total_page_faults > 0);
+
+    std::process::Command::new("true").status().unwrap();
+    s.refresh_kernel_stats();
+    let second = s.kernel_stats();
+    assert!(second.forks >= 1);
+    assert_eq!(second.total_forks, first.total_forks + second.forks,);
+    assert_eq!(
+        second.total_context_switches,
+        first.total_context_switches + second.context_switches,
+    );
+    assert!(second.total_oom_kills >= first.total_oom_kills);
+    assert!(second.total_swapped_in_pages >= first.total_swapped_in_pages);
+}
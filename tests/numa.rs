@@ -0,0 +1,26 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+#[test]
+fn test_numa_nodes() {
+    use sysinfo::SystemExt;
+
+    let s = sysinfo::System::new();
+    let nodes = s.numa_nodes();
+    if !cfg!(any(target_os = "linux", target_os = "android"))
+        || !std::path::Path::new("/sys/devices/system/node").exists()
+    {
+        assert!(nodes.is_empty());
+        return;
+    }
+    assert!(!nodes.is_empty());
+    for node in &nodes {
+        assert_eq!(node.distances.len(), nodes.len());
+        assert!(node.free_memory <= node.total_memory);
+        assert_eq!(node.used_memory, node.total_memory - node.free_memory);
+    }
+    assert!(nodes.iter().any(|node| !node.cpus.is_empty()));
+}
@@ -0,0 +1,47 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_pressure() {
+    use sysinfo::SystemExt;
+
+    let s = sysinfo::System::new();
+    // PSI can be disabled or missing in the kernel.
+    let pressure = match s.pressure() {
+        Some(pressure) => pressure,
+        None => {
+            assert!(!std::path::Path::new("/proc/pressure/cpu").exists());
+            return;
+        }
+    };
+    for resource in [pressure.cpu, pressure.memory, pressure.io]
+        .iter()
+        .flatten()
+    {
+        for stats in std::iter::once(&resource.some).chain(resource.full.as_ref()) {
+            assert!(stats.avg10 >= 0. && stats.avg10 <= 100.);
+            assert!(stats.avg60 >= 0. && stats.avg60 <= 100.);
+            assert!(stats.avg300 >= 0. && stats.avg300 <= 100.);
+        }
+        if let Some(full) = resource.full {
+            assert!(full.total <= resource.some.total);
+        }
+    }
+    assert!(pressure.cpu.is_some());
+    assert!(pressure.memory.expect("no memory pressure").full.is_some());
+    assert!(s.cgroup_pressure("/does/not/exist").is_none());
+}
+
+#[cfg(not(target_os = "linux"))]
+#[test]
+fn test_pressure() {
+    use sysinfo::SystemExt;
+
+    let s = sysinfo::System::new();
+    assert!(s.pressure().is_none());
+    assert!(s.cgroup_pressure("/").is_none());
+}
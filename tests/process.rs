@@ -147,3 +147,558 @@ fn cpu_usage_is_not_nan() {
     });
     assert!(checked > 0);
 }
+
+#[test]
+fn test_refresh_processes_specifics() {
+    use sysinfo::{ProcessRefreshKind, System};
+
+    let mut s = System::new();
+    s.refresh_processes_specifics(ProcessRefreshKind::new());
+    if !System::IS_SUPPORTED || cfg!(feature = "apple-sandbox") {
+        return;
+    }
+    assert!(!s.processes().is_empty());
+    #[cfg(target_os = "linux")]
+    {
+        let p = s
+            .process(sysinfo::get_current_pid().expect("failed to get current pid"))
+            .expect("failed to get process");
+        assert!(p.cmd().is_empty());
+        assert!(p.environ().is_empty());
+        assert!(p.tasks.is_empty());
+    }
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_refresh_processes_without_tasks() {
+    use sysinfo::{ProcessRefreshKind, System};
+
+    let pid = sysinfo::get_current_pid().expect("failed to get current pid");
+    // We need at least one other thread so the process has tasks.
+    let (tx, rx) = std::sync::mpsc::channel::<()>();
+    let handle = std::thread::spawn(move || rx.recv());
+
+    let mut s = System::new();
+    s.refresh_processes_specifics(ProcessRefreshKind::new().with_tasks());
+    assert!(!s
+        .process(pid)
+        .expect("failed to get process")
+        .tasks
+        .is_empty());
+
+    s.refresh_processes_specifics(ProcessRefreshKind::new());
+    assert!(s
+        .process(pid)
+        .expect("failed to get process")
+        .tasks
+        .is_empty());
+
+    tx.send(()).unwrap();
+    handle.join().unwrap().unwrap();
+}
+
+#[test]
+fn test_process_times() {
+    use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+    use sysinfo::{get_current_pid, System};
+
+    if !System::IS_SUPPORTED || cfg!(feature = "apple-sandbox") {
+        return;
+    }
+    // Burn a bit of CPU time so that it is measurable.
+    let start = Instant::now();
+    let mut x = 0u64;
+    while start.elapsed() < Duration::from_millis(100) {
+        x = x.wrapping_add(1);
+    }
+    assert!(x > 0);
+
+    let mut s = System::new();
+    let pid = get_current_pid().expect("failed to get current pid");
+    assert!(s.refresh_process(pid));
+    let p = s.process(pid).expect("failed to get process");
+
+    let start_time = p.start_time_precise();
+    assert!(start_time <= SystemTime::now());
+    let since_epoch = start_time.duration_since(UNIX_EPOCH).unwrap();
+    // Both values are computed from the same data but rounding can differ by one second.
+    assert!(
+        since_epoch.as_secs() + 1 >= p.start_time() && p.start_time() + 1 >= since_epoch.as_secs()
+    );
+    assert!(p.run_time() > Duration::from_secs(0));
+    #[cfg(not(windows))]
+    assert!(p.cpu_time().total() > Duration::from_secs(0));
+    assert_eq!(
+        p.cpu_time().total(),
+        p.cpu_time().user + p.cpu_time().system
+    );
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn test_process_scheduling() {
+    use sysinfo::{get_current_pid, ProcessRefreshKind, System};
+
+    let mut s = System::new();
+    let pid = get_current_pid().expect("failed to get current pid");
+    assert!(s.refresh_process_specifics(pid, ProcessRefreshKind::new().with_context_switches()));
+    let p = s.process(pid).expect("failed to get process");
+
+    let scheduling = p.scheduling();
+    assert!(scheduling.nice >= -20 && scheduling.nice <= 19);
+    assert!(!p.cpu_affinity().is_empty());
+    assert!(p.page_faults().total_minor > 0);
+    let switches = p.context_switches();
+    assert!(switches.total_voluntary + switches.total_involuntary > 0);
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn test_process_control() {
+    use std::time::Duration;
+    use sysinfo::{ProcessStatus, Signal, System};
+
+    let mut child = std::process::Command::new("sleep")
+        .arg("30")
+        .spawn()
+        .unwrap();
+    let pid = child.id() as sysinfo::Pid;
+    let mut s = System::new();
+    assert!(s.refresh_process(pid));
+
+    let p = s.process(pid).expect("failed to get process");
+    p.set_priority(10).expect("failed to set priority");
+    let cpu = p.cpu_affinity()[0];
+    p.set_cpu_affinity(&[cpu]).expect("failed to set affinity");
+    assert_eq!(p.cpu_affinity(), vec![cpu]);
+    p.suspend().expect("failed to suspend");
+    // Leave some time for the signal to be handled.
+    std::thread::sleep(Duration::from_millis(100));
+    assert!(s.refresh_process(pid));
+    let p = s.process(pid).expect("failed to get process");
+    assert_eq!(p.scheduling().nice, 10);
+    assert!(matches!(p.status(), ProcessStatus::Stop));
+    p.resume().expect("failed to resume");
+
+    assert!(!p.wait_for_exit(Some(Duration::from_millis(50))).unwrap());
+    p.try_kill(Signal::Kill).expect("failed to kill");
+    assert!(p.wait_for_exit(Some(Duration::from_secs(5))).unwrap());
+    child.wait().unwrap();
+    assert!(p.try_kill(Signal::Kill).is_err());
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn test_kill_tree() {
+    use std::time::Duration;
+    use sysinfo::{Signal, System};
+
+    let mut child = std::process::Command::new("sh")
+        .arg("-c")
+        .arg("sleep 30 & sleep 30; wait")
+        .spawn()
+        .unwrap();
+    let pid = child.id() as sysinfo::Pid;
+    // Leave some time for the children to be spawned.
+    std::thread::sleep(Duration::from_millis(250));
+    let mut s = System::new();
+    s.refresh_processes();
+
+    let mut signalled = s.kill_tree(pid, Signal::Kill).expect("failed to kill tree");
+    signalled.sort_unstable();
+    let mut expected = s
+        .processes()
+        .values()
+        .filter(|p| p.parent() == Some(pid))
+        .map(|p| p.pid())
+        .chain(std::iter::once(pid))
+        .collect::<Vec<_>>();
+    expected.sort_unstable();
+    assert_eq!(signalled, expected);
+    assert_eq!(signalled.len(), 3);
+    child.wait().unwrap();
+    for pid in signalled {
+        assert!(s
+            .process(pid)
+            .unwrap()
+            .wait_for_exit(Some(Duration::from_secs(5)))
+            .unwrap());
+    }
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn test_cmd_os_and_environ_os() {
+    use std::ffi::{OsStr, OsString};
+    use std::os::unix::ffi::OsStrExt;
+    use sysinfo::System;
+
+    let long_arg = "a".repeat(20_000);
+    let invalid_utf8 = OsStr::from_bytes(b"a\xffb");
+    let mut child = std::process::Command::new("sh")
+        .arg("-c")
+        .arg("sleep 3; true")
+        .arg(invalid_utf8)
+        .arg("")
+        .arg(&long_arg)
+        .env("SYSINFO_TEST_INVALID", invalid_utf8)
+        .env("SYSINFO_TEST_EQUAL", "a=b")
+        .spawn()
+        .unwrap();
+    let pid = child.id() as sysinfo::Pid;
+    // To ensure that the system data are filled correctly.
+    std::thread::sleep(std::time::Duration::from_millis(250));
+    let mut s = System::new();
+    assert!(s.refresh_process(pid));
+    let p = s.process(pid).expect("failed to get process");
+
+    let expected: Vec<OsString> = vec![
+        "sh".into(),
+        "-c".into(),
+        "sleep 3; true".into(),
+        invalid_utf8.into(),
+        "".into(),
+        long_arg.clone().into(),
+    ];
+    assert_eq!(p.cmd_os(), expected.as_slice());
+    // The lossy version still contains the long argument.
+    assert_eq!(p.cmd().last(), Some(&long_arg));
+
+    let map = p.environ_map();
+    assert_eq!(
+        map.get(OsStr::new("SYSINFO_TEST_INVALID"))
+            .map(|v| v.as_os_str()),
+        Some(invalid_utf8)
+    );
+    assert_eq!(
+        map.get(OsStr::new("SYSINFO_TEST_EQUAL"))
+            .map(|v| v.as_os_str()),
+        Some(OsStr::new("a=b"))
+    );
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
+
+#[test]
+fn test_process_filter() {
+    use sysinfo::{get_current_pid, Pattern, ProcessFilter, System};
+
+    let mut s = System::new();
+    s.refresh_processes();
+    if !System::IS_SUPPORTED || cfg!(feature = "apple-sandbox") {
+        return;
+    }
+    let pid = get_current_pid().expect("failed to get current pid");
+    let current = s.process(pid).expect("failed to get process");
+    let name = current.name().to_owned();
+    let status = current.status();
+    let user_id = current.user_id();
+
+    let filter = ProcessFilter::new().with_name(Pattern::exact(name.clone()));
+    assert!(s.processes_matching(&filter).any(|p| p.pid() == pid));
+    assert_eq!(
+        s.processes_matching(&filter).count(),
+        s.process_by_name(&name)
+            .into_iter()
+            .filter(|p| p.name() == name)
+            .count()
+    );
+
+    let filter = filter.with_predicate(move |p| p.pid() == pid);
+    assert_eq!(s.refresh_processes_matching(&filter), vec![pid]);
+
+    let filter = ProcessFilter::new()
+        .with_name(Pattern::exact(name))
+        .with_status(status)
+        .with_min_memory(1);
+    #[cfg(not(windows))]
+    let filter = filter.with_user(user_id.unwrap());
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    let filter = filter.with_cgroup(Pattern::glob("*"));
+    assert!(s.processes_matching(&filter).any(|p| p.pid() == pid));
+
+    let filter = ProcessFilter::new().with_name(Pattern::exact("no process has this name"));
+    assert_eq!(s.processes_matching(&filter).count(), 0);
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn test_refresh_processes_diff() {
+    use std::time::Duration;
+    use sysinfo::{ProcessRefreshKind, System};
+
+    // Processors are needed to detect dead processes on Linux.
+    let mut s = System::new();
+    s.refresh_cpu();
+    s.refresh_processes();
+    let mut child = std::process::Command::new("sh")
+        .arg("-c")
+        .arg("sleep 0.5; exec sleep 3")
+        .spawn()
+        .unwrap();
+    let pid = child.id() as sysinfo::Pid;
+
+    let diff = s.refresh_processes_diff(ProcessRefreshKind::everything());
+    assert!(diff.spawned.contains(&pid));
+    assert!(diff.exited.iter().all(|p| p.pid != pid));
+    assert_eq!(s.process(pid).unwrap().name(), "sh");
+
+    std::thread::sleep(Duration::from_millis(1000));
+    let diff = s.refresh_processes_diff(ProcessRefreshKind::everything());
+    assert!(!diff.spawned.contains(&pid));
+    let changed = diff
+        .changed
+        .iter()
+        .find(|p| p.pid == pid)
+        .expect("exec wasn't detected");
+    assert_eq!(changed.previous_name, "sh");
+    let p = s.process(pid).unwrap();
+    assert_eq!(p.name(), "sleep");
+    assert_eq!(p.cmd(), &["sleep", "3"]);
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+    let diff = s.refresh_processes_diff(ProcessRefreshKind::everything());
+    let exited = diff
+        .exited
+        .iter()
+        .find(|p| p.pid == pid)
+        .expect("exit wasn't detected");
+    assert_eq!(exited.name, "sleep");
+    assert_eq!(exited.cmd, &["sleep", "3"]);
+    assert!(s.process(pid).is_none());
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn test_refresh_processes_diff_same_name() {
+    use std::time::Duration;
+    use sysinfo::{ProcessRefreshKind, System};
+
+    let mut s = System::new();
+    s.refresh_cpu();
+    let mut child = std::process::Command::new("sh")
+        .arg("-c")
+        .arg("sleep 0.5; exec sh -c 'sleep 3; exit 0'")
+        .spawn()
+        .unwrap();
+    let pid = child.id() as sysinfo::Pid;
+    s.refresh_processes();
+    assert_eq!(s.process(pid).unwrap().name(), "sh");
+
+    std::thread::sleep(Duration::from_millis(1000));
+    let diff = s.refresh_processes_diff(ProcessRefreshKind::everything());
+    let changed = diff
+        .changed
+        .iter()
+        .find(|p| p.pid == pid)
+        .expect("exec wasn't detected");
+    assert_eq!(changed.previous_name, "sh");
+    assert_eq!(
+        changed.previous_cmd[2],
+        "sleep 0.5; exec sh -c 'sleep 3; exit 0'"
+    );
+    let p = s.process(pid).unwrap();
+    assert_eq!(p.name(), "sh");
+    assert_eq!(p.cmd(), &["sh", "-c", "sleep 3; exit 0"]);
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_process_watcher() {
+    use std::time::{Duration, Instant};
+    use sysinfo::{ProcessEvent, ProcessWatcher, System};
+
+    let watchers = [
+        ProcessWatcher::new(),
+        ProcessWatcher::new_polling().with_polling_interval(Duration::from_millis(50)),
+    ];
+    for mut watcher in watchers {
+        // Processors are needed to detect dead processes when polling.
+        let mut s = System::new();
+        s.refresh_cpu();
+        s.refresh_processes();
+        let mut child = std::process::Command::new("sh")
+            .arg("-c")
+            .arg("sleep 0.5; exit 3")
+            .spawn()
+            .unwrap();
+        let pid = child.id() as sysinfo::Pid;
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let mut forked = false;
+        let mut exit = None;
+        while exit.is_none() && Instant::now() < deadline {
+            for event in watcher
+                .wait_events(&mut s, Some(Duration::from_millis(100)))
+                .unwrap()
+            {
+                match event {
+                    ProcessEvent::Fork { pid: p, .. } if p == pid => {
+                        forked = true;
+                    }
+                    ProcessEvent::Exit { pid: p, .. } if p == pid => exit = Some(event),
+                    _ => {}
+                }
+            }
+            if forked && exit.is_none() {
+                assert!(s.process(pid).is_some());
+            }
+            // The exit is only visible in `/proc` once the process has been reaped.
+            let _ = child.try_wait();
+        }
+        assert!(forked, "fork wasn't detected");
+        let exit = exit.expect("exit wasn't detected");
+        assert!(s.process(pid).is_none());
+        child.wait().unwrap();
+        if !watcher.is_polling() {
+            assert_eq!(
+                exit,
+                ProcessEvent::Exit {
+                    pid,
+                    ppid: Some(sysinfo::get_current_pid().unwrap()),
+                    exit_code: Some(3),
+                    signal: None,
+                }
+            );
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_process_network_usage() {
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use sysinfo::{ProcessRefreshKind, System};
+
+    let pid = sysinfo::get_current_pid().unwrap();
+    let refresh_kind = ProcessRefreshKind::new().with_network_usage();
+    let mut s = System::new();
+    assert!(s.refresh_process_specifics(pid, refresh_kind));
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut server, _) = listener.accept().unwrap();
+    let data = vec![0u8; 100_000];
+    client.write_all(&data).unwrap();
+    let mut received = vec![0u8; data.len()];
+    server.read_exact(&mut received).unwrap();
+
+    assert!(s.refresh_process_specifics(pid, refresh_kind));
+    let usage = s.process(pid).unwrap().network_usage();
+    // Both ends of the connection belong to this process.
+    assert!(usage.transmitted_bytes >= 100_000);
+    assert!(usage.received_bytes >= 100_000);
+    assert!(usage.total_transmitted_bytes >= usage.transmitted_bytes);
+    assert!(usage.transmitted_packets > 0);
+
+    // It isn't refreshed by default.
+    assert!(s.refresh_process(pid));
+    assert_eq!(s.process(pid).unwrap().network_usage(), usage);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_process_io_stats() {
+    use std::io::Write;
+    use sysinfo::{ProcessRefreshKind, System};
+
+    let pid = sysinfo::get_current_pid().unwrap();
+    let refresh_kind = ProcessRefreshKind::new().with_disk_usage();
+    let mut s = System::new();
+    assert!(s.refresh_process_specifics(pid, refresh_kind));
+
+    let mut f = std::fs::File::create("/dev/null").unwrap();
+    for _ in 0..10 {
+        f.write_all(&[0; 1000]).unwrap();
+    }
+
+    assert!(s.refresh_process_specifics(pid, refresh_kind));
+    let io_stats = s.process(pid).unwrap().io_stats();
+    assert!(io_stats.written_chars >= 10_000);
+    assert!(io_stats.write_syscalls >= 10);
+    assert!(io_stats.total_written_chars >= io_stats.written_chars);
+    assert!(io_stats.total_read_syscalls > 0);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_process_namespaces() {
+    use sysinfo::{NamespaceKind, ProcessRefreshKind, System};
+
+    let pid = sysinfo::get_current_pid().unwrap();
+    let mut s = System::new();
+    assert!(s.refresh_process(pid));
+    assert_eq!(s.process(pid).unwrap().namespaces(), Default::default());
+    assert!(s.refresh_process_specifics(pid, ProcessRefreshKind::new().with_namespaces()));
+    let p = s.process(pid).unwrap();
+    let namespaces = p.namespaces();
+    assert!(namespaces.net.is_some());
+    assert!(namespaces.pid.is_some());
+    assert!(namespaces.mnt.is_some());
+    assert!(p.namespace_pid().is_some());
+
+    let net = namespaces.net.unwrap();
+    assert!(s
+        .processes_in_namespace(NamespaceKind::Net, net)
+        .contains(&pid));
+    assert!(s.processes_in_namespace(NamespaceKind::Net, 0).is_empty());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_systemd_units_usage() {
+    use sysinfo::System;
+
+    let mut s = System::new();
+    s.refresh_processes();
+    let units = s.systemd_units_usage();
+    let mut nb_processes = 0;
+    for (unit, usage) in &units {
+        assert!(unit.ends_with(".service") || unit.ends_with(".scope"));
+        for pid in &usage.processes {
+            let p = s.process(*pid).unwrap();
+            assert_eq!(p.systemd_unit(), Some(unit.as_str()));
+        }
+        nb_processes += usage.processes.len();
+    }
+    assert_eq!(
+        nb_processes,
+        s.processes()
+            .values()
+            .filter(|p| p.systemd_unit().is_some())
+            .count()
+    );
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_process_session() {
+    use sysinfo::System;
+
+    let mut child = std::process::Command::new("sleep")
+        .arg("3")
+        .spawn()
+        .unwrap();
+    let pid = sysinfo::get_current_pid().unwrap();
+    let child_pid = child.id() as sysinfo::Pid;
+    let mut s = System::new();
+    assert!(s.refresh_process(pid));
+    assert!(s.refresh_process(child_pid));
+    let p = s.process(pid).unwrap();
+    let c = s.process(child_pid).unwrap();
+    assert!(p.session_id().is_some());
+    assert!(p.process_group_id().is_some());
+    // The child inherits the session, the process group and the terminal.
+    assert_eq!(c.session_id(), p.session_id());
+    assert_eq!(c.process_group_id(), p.process_group_id());
+    assert_eq!(c.tty(), p.tty());
+    assert!(!c.is_session_leader());
+    child.kill().unwrap();
+    child.wait().unwrap();
+}
@@ -32,3 +32,88 @@ fn test_physical_core_numbers() {
         assert!(count.unwrap() > 0);
     }
 }
+
+#[test]
+fn test_cpu_topology() {
+    use sysinfo::SystemExt;
+
+    let s = sysinfo::System::new();
+    let topology = match s.cpu_topology() {
+        Some(topology) => topology,
+        None => {
+            assert!(!cfg!(any(target_os = "linux", target_os = "android")));
+            return;
+        }
+    };
+    assert_eq!(topology.cpus.len(), s.processors().len());
+    assert_eq!(
+        topology.cpus.iter().map(|cpu| cpu.id).collect::<Vec<_>>(),
+        topology.online
+    );
+    for cpu in &topology.cpus {
+        assert!(cpu.thread_siblings.contains(&cpu.id));
+        assert!(topology.possible.contains(&cpu.id));
+        for sibling in &cpu.thread_siblings {
+            assert_eq!(topology.cpu(*sibling).unwrap().core_id, cpu.core_id);
+        }
+        for cache in &cpu.caches {
+            assert!(cache.level > 0);
+            assert!(cache.shared_cpus.contains(&cpu.id));
+        }
+    }
+    let smt_siblings = topology
+        .cpus
+        .iter()
+        .filter(|cpu| cpu.is_smt_sibling())
+        .count();
+    assert!(smt_siblings < topology.cpus.len());
+}
+
+#[test]
+fn test_frequency_scaling() {
+    use sysinfo::{ProcessorExt, SystemExt};
+
+    let s = sysinfo::System::new();
+    assert_eq!(s.global_processor_info().frequency_scaling(), None);
+    for processor in s.processors() {
+        let scaling = match processor.frequency_scaling() {
+            Some(scaling) => scaling,
+            None => continue,
+        };
+        if let (Some(min), Some(max)) = (scaling.min_frequency, scaling.max_frequency) {
+            assert!(min <= max);
+        }
+        if let Some(governor) = &scaling.governor {
+            if !scaling.available_governors.is_empty() {
+                assert!(scaling.available_governors.contains(governor));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_cpu_info() {
+    use sysinfo::{ProcessorExt, SystemExt};
+
+    let s = sysinfo::System::new();
+    let info = match s.cpu_info() {
+        Some(info) => info,
+        None => {
+            assert!(!cfg!(any(target_os = "linux", target_os = "android")));
+            assert!(s.cpu_vulnerabilities().is_empty());
+            return;
+        }
+    };
+    assert_eq!(info.vendor_id, s.global_processor_info().vendor_id());
+    assert_eq!(info.brand, s.global_processor_info().brand());
+    if cfg!(target_arch = "x86_64") {
+        assert!(info.has_flag("sse2"));
+        assert!(info.family.is_some());
+    }
+    for (name, status) in s.cpu_vulnerabilities() {
+        assert!(!name.is_empty());
+        if let sysinfo::CpuVulnerability::Mitigated(mitigation) = status {
+            assert!(!mitigation.is_empty());
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+#[test]
+fn test_swap_devices() {
+    use sysinfo::{RefreshKind, SwapKind, SystemExt};
+
+    let s = sysinfo::System::new_with_specifics(RefreshKind::new().with_memory());
+    let devices = s.swap_devices();
+    if !cfg!(any(target_os = "linux", target_os = "android")) {
+        assert!(devices.is_empty());
+        return;
+    }
+    assert_eq!(
+        devices.iter().map(|device| device.size).sum::<u64>(),
+        s.total_swap()
+    );
+    for (i, device) in devices.iter().enumerate() {
+        assert!(device.used <= device.size);
+        if device.zram.is_some() {
+            assert_eq!(device.kind, SwapKind::Zram);
+        }
+        if i > 0 {
+            assert!(devices[i - 1].priority >= device.priority);
+        }
+    }
+}